@@ -1,3 +1,158 @@
+use crate::game::player::components::PlayerId;
+use crate::game::surface::components::Surface;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
 pub struct GameOver {
     pub score_value: usize,
+    pub players: Vec<PlayerResult>,
+    // Seed the run was played with, it leads to the run's replay.
+    pub seed: u64,
+}
+
+// Per-player breakdown of the finished run.
+#[derive(Debug, Clone)]
+pub struct PlayerResult {
+    pub id: PlayerId,
+    pub score: usize,
+}
+
+// Asks the enemy plugin to spawn an enemy at the given position, moving in the given direction.
+pub struct SpawnEnemy {
+    pub position: Vec3,
+    pub direction: Vec3,
+}
+
+// Asks the player plugin to spawn the given player mid-game, e.g. when a client joins the server.
+pub struct SpawnPlayer {
+    pub id: PlayerId,
+    pub remote: bool,
+}
+
+pub struct StarCollected {
+    // Where the star was.
+    pub position: Vec3,
+    // Points awarded for the star, the multiplier already applied.
+    pub points: usize,
+    pub multiplier: usize,
+}
+
+// Sent when something hurts the given player entity.
+pub struct PlayerHit {
+    pub player_entity: Entity,
+    pub source: HitSource,
+}
+
+// Kind of enemy that hit the player.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+pub enum HitSource {
+    Enemy,
+    Boss,
+}
+
+// Sent when a hit took the player's last life.
+pub struct PlayerOut {
+    pub source: HitSource,
+}
+
+// Sent once for every newly unlocked achievement, with the sprite to show for it.
+pub struct AchievementUnlocked {
+    pub icon: String,
+}
+
+// Asks the audio manager to play a sound. Sounds happening somewhere in the arena come with
+// their position, so they can be heard from that side.
+pub struct SoundEvent {
+    pub sound: Sound,
+    pub position: Option<Vec3>,
+}
+
+impl SoundEvent {
+    pub fn at(sound: Sound, position: Vec3) -> Self {
+        SoundEvent {
+            sound,
+            position: Some(position),
+        }
+    }
+}
+
+impl From<Sound> for SoundEvent {
+    fn from(sound: Sound) -> Self {
+        SoundEvent {
+            sound,
+            position: None,
+        }
+    }
+}
+
+// Asks the camera to react to something that happened. The strengths are scaled by the
+// screen shake setting.
+pub enum CameraEffect {
+    // Adds trauma, the camera shakes with its square. Full trauma is 1.
+    Shake(f32),
+    // Stops the game for the given seconds, so a hit lands harder.
+    HitStop(f32),
+    // Zooms in by the given fraction and eases back out.
+    ZoomPunch(f32),
+}
+
+// Asks for a burst of particles where something happened.
+pub struct ParticleBurst {
+    pub effect: ParticleEffect,
+    pub position: Vec3,
+    // Where the particles fly to, the preset's spread goes around it. Any way without it.
+    pub direction: Option<Vec3>,
+}
+
+impl ParticleBurst {
+    pub fn at(effect: ParticleEffect, position: Vec3) -> Self {
+        ParticleBurst {
+            effect,
+            position,
+            direction: None,
+        }
+    }
+
+    pub fn towards(effect: ParticleEffect, position: Vec3, direction: Vec3) -> Self {
+        ParticleBurst {
+            effect,
+            position,
+            direction: Some(direction),
+        }
+    }
+}
+
+// What each burst looks like is set up in PARTICLES_CONFIG_PATH.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize)]
+pub enum ParticleEffect {
+    StarCollected,
+    EnemyBounce,
+    EnemySpawned,
+    EnemyDestroyed,
+    PlayerOut,
+}
+
+// What each sound sounds like is set up in SOUNDS_CONFIG_PATH.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize)]
+pub enum Sound {
+    EnemyBounce,
+    Dash,
+    PlayerHit,
+    PlayerOut,
+    StarCollected,
+    Shot,
+    OutOfAmmo,
+    EnemyDestroyed,
+    BossAppeared,
+    BossHit,
+    BossDefeated,
+    KeyCollected,
+    ExitUnlocked,
+    LevelCompleted,
+    PlayersBumped,
+    VersusHit,
+    AchievementUnlocked,
+    SettingChanged,
+    Footstep(Surface),
 }
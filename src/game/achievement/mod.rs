@@ -0,0 +1,40 @@
+pub mod resources;
+mod systems;
+
+use super::replay::not_watching_replay;
+use crate::events::AchievementUnlocked;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const ACHIEVEMENTS_CONFIG_PATH: &str = "assets/config/achievements.ron";
+pub const ACHIEVEMENTS_SAVE_PATH: &str = "achievements.ron";
+
+pub struct AchievementPlugin;
+
+impl Plugin for AchievementPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Achievements>()
+            .init_resource::<AchievementProgress>()
+            .init_resource::<RunProgress>()
+            .add_event::<AchievementUnlocked>()
+            .add_startup_system(load_achievements)
+            .add_startup_system(load_achievement_progress)
+            .add_system(reset_run_progress.in_schedule(OnEnter(AppState::Game)))
+            // A watched replay doesn't earn anything.
+            .add_systems(
+                (
+                    track_run_progress.run_if(in_state(AppState::Game)),
+                    track_game_over,
+                    unlock_achievements
+                        .after(track_run_progress)
+                        .after(track_game_over),
+                )
+                    .distributive_run_if(not_watching_replay),
+            )
+            // The counters are saved once the run is over, unlocks are saved right away.
+            .add_system(save_achievement_progress.run_if(not(in_state(AppState::Game))));
+    }
+}
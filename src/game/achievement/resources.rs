@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// What has to happen for an achievement to unlock.
+#[derive(Debug, Clone, Deserialize)]
+pub enum AchievementCondition {
+    // Stars collected over all runs.
+    StarsCollected(usize),
+    StarsInRun(usize),
+    GamesPlayed(usize),
+    ScoreAtLeast(usize),
+    SurviveWithEnemies { seconds: f32, enemies: usize },
+    NoHitWave(usize),
+}
+
+impl AchievementCondition {
+    pub fn is_met(&self, progress: &AchievementProgress, run: &RunProgress) -> bool {
+        match *self {
+            AchievementCondition::StarsCollected(count) => progress.stars_collected >= count,
+            AchievementCondition::StarsInRun(count) => run.stars >= count,
+            AchievementCondition::GamesPlayed(count) => progress.games_played >= count,
+            AchievementCondition::ScoreAtLeast(score) => run
+                .final_score
+                .is_some_and(|final_score| final_score >= score),
+            AchievementCondition::SurviveWithEnemies { seconds, enemies } => {
+                run.seconds >= seconds && run.enemies >= enemies
+            }
+            AchievementCondition::NoHitWave(wave) => run.wave >= wave && !run.hit,
+        }
+    }
+
+    // How far along the counters kept between runs are, as (current, target).
+    pub fn lifetime_progress(&self, progress: &AchievementProgress) -> Option<(usize, usize)> {
+        match *self {
+            AchievementCondition::StarsCollected(count) => {
+                Some((progress.stars_collected.min(count), count))
+            }
+            AchievementCondition::GamesPlayed(count) => {
+                Some((progress.games_played.min(count), count))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AchievementDefinition {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    pub condition: AchievementCondition,
+}
+
+// Every achievement there is, read from the achievements config.
+#[derive(Resource, Default)]
+pub struct Achievements {
+    pub definitions: Vec<AchievementDefinition>,
+}
+
+// Saved between launches.
+#[derive(Resource, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AchievementProgress {
+    // Ids of the unlocked achievements.
+    pub unlocked: Vec<String>,
+    pub stars_collected: usize,
+    pub games_played: usize,
+}
+
+impl AchievementProgress {
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.iter().any(|unlocked_id| unlocked_id == id)
+    }
+}
+
+// How the current run is going.
+#[derive(Resource, Debug, Default)]
+pub struct RunProgress {
+    pub stars: usize,
+    pub seconds: f32,
+    // Enemies around right now.
+    pub enemies: usize,
+    pub wave: usize,
+    pub hit: bool,
+    // Set once the run is over.
+    pub final_score: Option<usize>,
+}
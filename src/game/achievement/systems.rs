@@ -0,0 +1,131 @@
+use super::resources::*;
+use super::{ACHIEVEMENTS_CONFIG_PATH, ACHIEVEMENTS_SAVE_PATH};
+use crate::events::{AchievementUnlocked, GameOver, PlayerHit, StarCollected};
+use crate::game::enemy::components::Enemy;
+use crate::game::enemy::resources::Wave;
+use crate::game::simulation::resources::SimulationClock;
+use crate::game::simulation::SIMULATION_TICK_RATE;
+
+use bevy::prelude::*;
+
+pub fn load_achievements(mut achievements: ResMut<Achievements>) {
+    let config = match std::fs::read_to_string(ACHIEVEMENTS_CONFIG_PATH) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not read {}: {}", ACHIEVEMENTS_CONFIG_PATH, error);
+            return;
+        }
+    };
+
+    match ron::from_str::<Vec<AchievementDefinition>>(&config) {
+        Ok(v) => achievements.definitions = v,
+        Err(error) => println!("Could not parse {}: {}", ACHIEVEMENTS_CONFIG_PATH, error),
+    }
+}
+
+// Reads the unlocked achievements, there's nothing to read before the first launch.
+pub fn load_achievement_progress(mut achievement_progress: ResMut<AchievementProgress>) {
+    let progress = match std::fs::read_to_string(ACHIEVEMENTS_SAVE_PATH) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    match ron::from_str::<AchievementProgress>(&progress) {
+        Ok(v) => *achievement_progress = v,
+        Err(error) => println!("Could not parse {}: {}", ACHIEVEMENTS_SAVE_PATH, error),
+    }
+}
+
+fn write_achievement_progress(achievement_progress: &AchievementProgress) {
+    let progress = match ron::ser::to_string_pretty(achievement_progress, default()) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not serialize the achievements: {}", error);
+            return;
+        }
+    };
+
+    if let Err(error) = std::fs::write(ACHIEVEMENTS_SAVE_PATH, progress) {
+        println!("Could not write {}: {}", ACHIEVEMENTS_SAVE_PATH, error);
+    }
+}
+
+pub fn save_achievement_progress(achievement_progress: Res<AchievementProgress>) {
+    if achievement_progress.is_changed() && !achievement_progress.is_added() {
+        write_achievement_progress(&achievement_progress);
+    }
+}
+
+pub fn reset_run_progress(mut run_progress: ResMut<RunProgress>) {
+    *run_progress = RunProgress::default();
+}
+
+pub fn track_run_progress(
+    mut star_collected_event_reader: EventReader<StarCollected>,
+    mut player_hit_event_reader: EventReader<PlayerHit>,
+    enemy_query: Query<(), With<Enemy>>,
+    wave: Res<Wave>,
+    simulation_clock: Res<SimulationClock>,
+    mut run_progress: ResMut<RunProgress>,
+    mut achievement_progress: ResMut<AchievementProgress>,
+) {
+    let stars_collected = star_collected_event_reader.iter().count();
+    if stars_collected > 0 {
+        run_progress.stars += stars_collected;
+        achievement_progress.stars_collected += stars_collected;
+    }
+    if player_hit_event_reader.iter().next().is_some() {
+        run_progress.hit = true;
+    }
+
+    run_progress.enemies = enemy_query.iter().count();
+    run_progress.wave = wave.number;
+    run_progress.seconds = simulation_clock.tick as f32 / SIMULATION_TICK_RATE as f32;
+}
+
+pub fn track_game_over(
+    mut game_over_event_reader: EventReader<GameOver>,
+    mut run_progress: ResMut<RunProgress>,
+    mut achievement_progress: ResMut<AchievementProgress>,
+) {
+    if let Some(event) = game_over_event_reader.iter().next() {
+        run_progress.final_score = Some(event.score_value);
+        achievement_progress.games_played += 1;
+    }
+}
+
+pub fn unlock_achievements(
+    achievements: Res<Achievements>,
+    run_progress: Res<RunProgress>,
+    mut achievement_progress: ResMut<AchievementProgress>,
+    mut achievement_unlocked_event_writer: EventWriter<AchievementUnlocked>,
+) {
+    if !run_progress.is_changed() && !achievement_progress.is_changed() {
+        return;
+    }
+
+    let mut unlocked_any = false;
+    for definition in achievements.definitions.iter() {
+        if achievement_progress.is_unlocked(&definition.id)
+            || !definition
+                .condition
+                .is_met(&achievement_progress, &run_progress)
+        {
+            continue;
+        }
+
+        println!(
+            "Achievement unlocked: {} - {}",
+            definition.name, definition.description
+        );
+        achievement_progress.unlocked.push(definition.id.clone());
+        achievement_unlocked_event_writer.send(AchievementUnlocked {
+            icon: definition.icon.clone(),
+        });
+        unlocked_any = true;
+    }
+
+    if unlocked_any {
+        write_achievement_progress(&achievement_progress);
+    }
+}
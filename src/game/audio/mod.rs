@@ -0,0 +1,32 @@
+pub mod resources;
+mod systems;
+
+use super::simulation::run_simulation;
+use crate::events::SoundEvent;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const SOUNDS_CONFIG_PATH: &str = "assets/config/sounds.ron";
+// Positional sounds are placed in front of the listener, a sound HEARING_DISTANCE pixels to
+// the side is right in front of that ear. The further from the listener, the quieter it gets.
+pub const HEARING_DISTANCE: f32 = 400.0;
+pub const EAR_GAP: f32 = 2.0;
+pub const EMITTER_DEPTH: f32 = 1.0;
+pub const SPATIAL_GAIN: f32 = 2.0; // Makes up for the depth, the sounds right in front would play at half volume.
+
+// Plays the sounds asked for with SoundEvents. Gameplay only says what happened,
+// the sounds, their variations and volumes are up to the audio manager.
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SoundLibrary>()
+            .init_resource::<PlayingSounds>()
+            .add_event::<SoundEvent>()
+            .add_startup_system(load_sound_library)
+            // The ticks of this frame ask for their sounds while the simulation runs
+            .add_system(play_sounds.after(run_simulation));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::events::Sound;
+use crate::settings::resources::Settings;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+use std::collections::VecDeque;
+
+// Group of sounds sharing a volume setting.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Deserialize)]
+pub enum Bus {
+    #[default]
+    Sfx,
+    Music,
+    Ui,
+}
+
+impl Bus {
+    // Volume of the bus, scaled by the master volume. Full volume without any settings,
+    // like in the headless apps.
+    pub fn volume(&self, settings: Option<&Settings>) -> f32 {
+        let settings = match settings {
+            Some(v) => v,
+            None => return 1.0,
+        };
+
+        let bus_volume = match self {
+            Bus::Sfx => settings.sfx_volume,
+            Bus::Music => settings.music_volume,
+            Bus::Ui => settings.ui_volume,
+        };
+        settings.master_volume * bus_volume
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SoundDefinition {
+    // One of the variations is picked every time the sound plays.
+    pub paths: Vec<String>,
+    pub bus: Bus,
+    pub volume: f32,
+    // How many copies of the sound can play at once, each counting for `length` seconds.
+    pub max_instances: usize,
+    pub length: f32,
+    pub pitch_variation: f32,
+    pub volume_variation: f32,
+}
+
+impl Default for SoundDefinition {
+    fn default() -> Self {
+        SoundDefinition {
+            paths: Vec::new(),
+            bus: Bus::Sfx,
+            volume: 1.0,
+            max_instances: 4,
+            length: 0.5,
+            pitch_variation: 0.0,
+            volume_variation: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SoundsConfig {
+    pub sounds: HashMap<Sound, SoundDefinition>,
+}
+
+// Every sound with its variations, loaded up front so nothing gets loaded mid-game.
+#[derive(Resource, Default)]
+pub struct SoundLibrary {
+    pub sounds: HashMap<Sound, (SoundDefinition, Vec<Handle<AudioSource>>)>,
+    // Variations the arena theme plays instead of the usual ones.
+    pub themed: HashMap<Sound, Vec<Handle<AudioSource>>>,
+}
+
+// When the copies of every sound started playing, in seconds since the startup.
+#[derive(Resource, Default)]
+pub struct PlayingSounds {
+    pub started: HashMap<Sound, VecDeque<f64>>,
+}
@@ -0,0 +1,120 @@
+use super::resources::*;
+use super::{EAR_GAP, EMITTER_DEPTH, HEARING_DISTANCE, SOUNDS_CONFIG_PATH, SPATIAL_GAIN};
+use crate::events::SoundEvent;
+use crate::game::player::components::Player;
+use crate::settings::resources::Settings;
+
+use bevy::prelude::*;
+use rand::prelude::*;
+
+// Reads the sound definitions and starts loading every variation.
+pub fn load_sound_library(mut sound_library: ResMut<SoundLibrary>, asset_server: Res<AssetServer>) {
+    let config = match std::fs::read_to_string(SOUNDS_CONFIG_PATH) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not read {}: {}", SOUNDS_CONFIG_PATH, error);
+            return;
+        }
+    };
+    let config = match ron::from_str::<SoundsConfig>(&config) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not parse {}: {}", SOUNDS_CONFIG_PATH, error);
+            return;
+        }
+    };
+
+    for (sound, definition) in config.sounds {
+        let handles = definition
+            .paths
+            .iter()
+            .map(|path| asset_server.load(path.as_str()))
+            .collect();
+        sound_library.sounds.insert(sound, (definition, handles));
+    }
+}
+
+// Plays the requested sounds at their bus volume, with a bit of random variation.
+// Sounds already playing too many times at once are skipped.
+// Positional sounds are heard from where they happened, relative to the players or,
+// without any players around, to the camera.
+#[allow(clippy::too_many_arguments)]
+pub fn play_sounds(
+    mut sound_event_reader: EventReader<SoundEvent>,
+    player_query: Query<&Transform, With<Player>>,
+    camera_query: Query<&Transform, With<Camera>>,
+    sound_library: Res<SoundLibrary>,
+    mut playing_sounds: ResMut<PlayingSounds>,
+    audio: Res<Audio>,
+    settings: Option<Res<Settings>>,
+    time: Res<Time>,
+) {
+    // Audio isn't part of the simulation, it doesn't touch the game's random number generator.
+    let mut rng = thread_rng();
+    let now = time.elapsed_seconds_f64();
+
+    let listener = if player_query.is_empty() {
+        camera_query
+            .iter()
+            .next()
+            .map_or(Vec3::ZERO, |camera_transform| camera_transform.translation)
+    } else {
+        player_query
+            .iter()
+            .map(|player_transform| player_transform.translation)
+            .sum::<Vec3>()
+            / player_query.iter().count() as f32
+    };
+
+    for event in sound_event_reader.iter() {
+        let sound = &event.sound;
+        let (definition, handles) = match sound_library.sounds.get(sound) {
+            Some(v) => v,
+            None => continue,
+        };
+        let handles = sound_library.themed.get(sound).unwrap_or(handles);
+        let handle = match handles.choose(&mut rng) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let started = playing_sounds.started.entry(*sound).or_default();
+        while started
+            .front()
+            .is_some_and(|start| now - start >= definition.length as f64)
+        {
+            started.pop_front();
+        }
+        if started.len() >= definition.max_instances {
+            continue;
+        }
+        started.push_back(now);
+
+        let vary =
+            |rng: &mut ThreadRng, variation: f32| 1.0 + rng.gen_range(-1.0..=1.0) * variation;
+        let volume = definition.volume
+            * definition.bus.volume(settings.as_deref())
+            * vary(&mut rng, definition.volume_variation);
+        let speed = vary(&mut rng, definition.pitch_variation);
+
+        let playback_settings = PlaybackSettings::ONCE
+            .with_volume(volume.max(0.0))
+            .with_speed(speed);
+
+        match event.position {
+            Some(position) => {
+                let offset = (position - listener).truncate() / HEARING_DISTANCE;
+                audio.play_spatial_with_settings(
+                    handle.clone(),
+                    playback_settings.with_volume(playback_settings.volume * SPATIAL_GAIN),
+                    Transform::IDENTITY,
+                    EAR_GAP,
+                    offset.extend(EMITTER_DEPTH),
+                );
+            }
+            None => {
+                audio.play_with_settings(handle.clone(), playback_settings);
+            }
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+#[derive(Component, Clone)]
+pub struct Boss {
+    pub health: usize,
+    pub phase: BossPhase,
+    pub direction: Vec3,
+    // Counts down to the next charge while chasing, and the charge itself while charging.
+    pub charge_timer: Timer,
+    pub charging: bool,
+    pub minion_timer: Timer,
+}
+
+// The boss gets more aggressive as it loses health.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BossPhase {
+    // Slowly follows the player and charges from time to time.
+    Chase,
+    // Charges more often and faster.
+    Rage,
+    // Keeps charging and summons minions.
+    Summon,
+}
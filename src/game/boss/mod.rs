@@ -0,0 +1,43 @@
+pub mod components;
+mod systems;
+
+use super::simulation::{RngSet, SimulationSchedule};
+use crate::AppState;
+use systems::*;
+
+pub use systems::boss_bundle;
+
+use bevy::prelude::*;
+
+pub const BOSS_WAVE_INTERVAL: usize = 5; // Boss appears every BOSS_WAVE_INTERVAL waves.
+pub const BOSS_HEALTH: usize = 6;
+pub const BOSS_SIZE: f32 = 128.0;
+pub const BOSS_SPEED: f32 = 120.0;
+pub const BOSS_CHARGE_SPEED: f32 = 900.0;
+pub const BOSS_CHARGE_PERIOD: f32 = 3.0;
+pub const BOSS_CHARGE_DURATION: f32 = 1.5;
+pub const BOSS_MINION_PERIOD: f32 = 4.0;
+pub const BOSS_SCORE: usize = 50;
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Upon leaving the game state, despawn the boss
+            .add_system(despawn_boss.in_schedule(OnExit(AppState::Game)))
+            .add_systems(
+                (
+                    spawn_boss_on_wave,
+                    update_boss_phase,
+                    move_boss.after(update_boss_phase),
+                    check_boss_wall_hit.after(move_boss),
+                    spawn_minions
+                        .after(update_boss_phase)
+                        .in_set(RngSet::Minions),
+                    check_boss_collision.after(move_boss),
+                )
+                    .in_schedule(SimulationSchedule),
+            );
+    }
+}
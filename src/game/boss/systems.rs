@@ -0,0 +1,285 @@
+use super::components::*;
+use super::{
+    BOSS_CHARGE_DURATION, BOSS_CHARGE_PERIOD, BOSS_CHARGE_SPEED, BOSS_HEALTH, BOSS_MINION_PERIOD,
+    BOSS_SCORE, BOSS_SIZE, BOSS_SPEED, BOSS_WAVE_INTERVAL,
+};
+use crate::events::{HitSource, PlayerHit, Sound, SoundEvent, SpawnEnemy};
+use crate::game::enemy::resources::Wave;
+use crate::game::player::components::{Dash, Invulnerable, Player};
+use crate::game::player::PLAYER_SIZE;
+use crate::game::score::resources::Score;
+use crate::game::simulation::resources::GameRng;
+use crate::game::{ARENA_HEIGHT, ARENA_WIDTH};
+
+use bevy::prelude::*;
+use rand::prelude::*;
+
+// Spawns the boss in the corner furthest from the players on every BOSS_WAVE_INTERVAL wave.
+pub fn spawn_boss_on_wave(
+    mut commands: Commands,
+    player_query: Query<&Transform, With<Player>>,
+    boss_query: Query<&Boss>,
+    asset_server: Res<AssetServer>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    wave: Res<Wave>,
+) {
+    if !wave.is_changed() || wave.number == 0 || !wave.number.is_multiple_of(BOSS_WAVE_INTERVAL) {
+        return;
+    }
+    // Only one boss at a time.
+    if !boss_query.is_empty() {
+        return;
+    }
+
+    let boss_half_size = BOSS_SIZE / 2.0;
+    // Average position of the players, or the center of the arena when nobody is left.
+    let player_translation = if player_query.is_empty() {
+        Vec3::new(ARENA_WIDTH, ARENA_HEIGHT, 0.0) / 2.0
+    } else {
+        player_query
+            .iter()
+            .map(|player_transform| player_transform.translation)
+            .sum::<Vec3>()
+            / player_query.iter().count() as f32
+    };
+
+    let x_pos = if player_translation.x < ARENA_WIDTH / 2.0 {
+        ARENA_WIDTH - boss_half_size
+    } else {
+        boss_half_size
+    };
+    let y_pos = if player_translation.y < ARENA_HEIGHT / 2.0 {
+        ARENA_HEIGHT - boss_half_size
+    } else {
+        boss_half_size
+    };
+
+    commands.spawn(boss_bundle(
+        Transform::from_xyz(x_pos, y_pos, 0.0),
+        Boss {
+            health: BOSS_HEALTH,
+            phase: BossPhase::Chase,
+            direction: Vec3::ZERO,
+            charge_timer: Timer::from_seconds(BOSS_CHARGE_PERIOD, TimerMode::Once),
+            charging: false,
+            minion_timer: Timer::from_seconds(BOSS_MINION_PERIOD, TimerMode::Repeating),
+        },
+        &asset_server,
+    ));
+
+    sound_event_writer.send(SoundEvent::at(
+        Sound::BossAppeared,
+        Vec3::new(x_pos, y_pos, 0.0),
+    ));
+    println!("Boss appeared on wave {}!", wave.number);
+}
+
+pub fn boss_bundle(
+    transform: Transform,
+    boss: Boss,
+    asset_server: &AssetServer,
+) -> (SpriteBundle, Boss) {
+    (
+        SpriteBundle {
+            transform: transform.with_scale(Vec3::splat(2.0)),
+            texture: asset_server.load("sprites/Default/ball_red_large_alt.png"),
+            ..default()
+        },
+        boss,
+    )
+}
+
+// Switches the boss phase depending on the health left.
+pub fn update_boss_phase(mut boss_query: Query<&mut Boss>) {
+    for mut boss in boss_query.iter_mut() {
+        let phase = if boss.health * 3 > BOSS_HEALTH * 2 {
+            BossPhase::Chase
+        } else if boss.health * 3 > BOSS_HEALTH {
+            BossPhase::Rage
+        } else {
+            BossPhase::Summon
+        };
+
+        if boss.phase != phase {
+            boss.phase = phase;
+            println!("Boss entered {:?} phase!", phase);
+        }
+    }
+}
+
+// Follows the closest player and charges at them once the charge timer runs out.
+pub fn move_boss(
+    mut boss_query: Query<(&mut Transform, &mut Boss)>,
+    player_query: Query<&Transform, (With<Player>, Without<Boss>)>,
+    time: Res<Time>,
+) {
+    for (mut transform, mut boss) in boss_query.iter_mut() {
+        let player_translation = player_query
+            .iter()
+            .map(|player_transform| player_transform.translation)
+            .min_by(|a, b| {
+                a.distance(transform.translation)
+                    .total_cmp(&b.distance(transform.translation))
+            });
+
+        boss.charge_timer.tick(time.delta());
+
+        let charge_speed_multiplier = if boss.phase == BossPhase::Chase {
+            1.0
+        } else {
+            1.3
+        };
+
+        if boss.charge_timer.finished() {
+            if boss.charging {
+                stop_charge(&mut boss);
+            } else {
+                boss.charging = true;
+                boss.charge_timer = Timer::from_seconds(BOSS_CHARGE_DURATION, TimerMode::Once);
+            }
+        }
+
+        if !boss.charging {
+            if let Some(player_translation) = player_translation {
+                boss.direction = (player_translation - transform.translation).normalize_or_zero();
+            }
+        }
+
+        let speed = if boss.charging {
+            BOSS_CHARGE_SPEED * charge_speed_multiplier
+        } else {
+            BOSS_SPEED
+        };
+        transform.translation += boss.direction * speed * time.delta_seconds();
+    }
+}
+
+// Ends the charge and schedules the next one, the enraged boss charges twice as often.
+fn stop_charge(boss: &mut Boss) {
+    let charge_period = if boss.phase == BossPhase::Chase {
+        BOSS_CHARGE_PERIOD
+    } else {
+        BOSS_CHARGE_PERIOD / 2.0
+    };
+
+    boss.charging = false;
+    boss.charge_timer = Timer::from_seconds(charge_period, TimerMode::Once);
+}
+
+// The boss gets hurt when it slams into a wall while charging, luring it there is
+// the only way to defeat it. The kill goes to the player nearest to the final slam.
+pub fn check_boss_wall_hit(
+    mut commands: Commands,
+    mut boss_query: Query<(Entity, &mut Transform, &mut Boss), Without<Player>>,
+    player_query: Query<(&Transform, &Player)>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    mut score: ResMut<Score>,
+) {
+    let boss_half_size = BOSS_SIZE / 2.0;
+    let x_min = 0.0 + boss_half_size;
+    let x_max = ARENA_WIDTH - boss_half_size;
+    let y_min = 0.0 + boss_half_size;
+    let y_max = ARENA_HEIGHT - boss_half_size;
+
+    for (boss_entity, mut transform, mut boss) in boss_query.iter_mut() {
+        let translation = transform.translation;
+        let clamped_translation = Vec3::new(
+            translation.x.clamp(x_min, x_max),
+            translation.y.clamp(y_min, y_max),
+            translation.z,
+        );
+
+        if clamped_translation == translation {
+            continue;
+        }
+        transform.translation = clamped_translation;
+
+        if !boss.charging {
+            continue;
+        }
+
+        boss.health -= 1;
+        stop_charge(&mut boss);
+
+        if boss.health > 0 {
+            sound_event_writer.send(SoundEvent::at(Sound::BossHit, transform.translation));
+            continue;
+        }
+
+        // Boss is defeated.
+        let nearest_player = player_query.iter().min_by(|(a, _), (b, _)| {
+            let distance = |player_transform: &Transform| {
+                player_transform.translation.distance(transform.translation)
+            };
+            distance(a).total_cmp(&distance(b))
+        });
+        if let Some((_, player)) = nearest_player {
+            score.award(player.id, BOSS_SCORE);
+        }
+
+        sound_event_writer.send(SoundEvent::at(Sound::BossDefeated, transform.translation));
+        println!("Boss defeated! +{} score", BOSS_SCORE);
+
+        commands.entity(boss_entity).despawn();
+    }
+}
+
+// In the last phase the boss summons minions in random directions.
+pub fn spawn_minions(
+    mut boss_query: Query<(&Transform, &mut Boss)>,
+    mut spawn_enemy_event_writer: EventWriter<SpawnEnemy>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+) {
+    for (transform, mut boss) in boss_query.iter_mut() {
+        if boss.phase != BossPhase::Summon {
+            continue;
+        }
+
+        boss.minion_timer.tick(time.delta());
+        if !boss.minion_timer.just_finished() {
+            continue;
+        }
+
+        let direction = Vec3::new(
+            game_rng.rng.gen::<f32>() - 0.5,
+            game_rng.rng.gen::<f32>() - 0.5,
+            0.0,
+        );
+        spawn_enemy_event_writer.send(SpawnEnemy {
+            position: transform.translation,
+            direction: direction.normalize_or_zero(),
+        });
+    }
+}
+
+// Checks if the players and boss collide with each other.
+// Dashing and recently hit players are invulnerable.
+#[allow(clippy::type_complexity)]
+pub fn check_boss_collision(
+    player_query: Query<(Entity, &Transform), (With<Player>, Without<Dash>, Without<Invulnerable>)>,
+    boss_query: Query<&Transform, With<Boss>>,
+    mut player_hit_event_writer: EventWriter<PlayerHit>,
+) {
+    for (player_entity, player_transform) in player_query.iter() {
+        for boss_transform in boss_query.iter() {
+            if player_transform
+                .translation
+                .distance(boss_transform.translation)
+                < PLAYER_SIZE / 2.0 + BOSS_SIZE / 2.0
+            {
+                player_hit_event_writer.send(PlayerHit {
+                    player_entity,
+                    source: HitSource::Boss,
+                });
+                break;
+            }
+        }
+    }
+}
+
+pub fn despawn_boss(mut commands: Commands, boss_query: Query<Entity, With<Boss>>) {
+    for boss_entity in boss_query.iter() {
+        commands.entity(boss_entity).despawn();
+    }
+}
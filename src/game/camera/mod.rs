@@ -0,0 +1,43 @@
+pub mod resources;
+mod systems;
+
+use super::simulation::run_simulation;
+use crate::events::CameraEffect;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const MAX_SHAKE_OFFSET: f32 = 24.0;
+pub const MAX_SHAKE_ANGLE: f32 = 0.04; // In radians.
+pub const SHAKE_FREQUENCY: f32 = 20.0;
+pub const TRAUMA_DECAY: f32 = 1.5; // Trauma lost every second.
+pub const ZOOM_DECAY: f32 = 6.0; // How quickly a zoom punch eases back out.
+pub const HIT_TRAUMA: f32 = 0.4;
+pub const OUT_TRAUMA: f32 = 0.7;
+pub const OUT_HIT_STOP: f32 = 0.12;
+pub const BIG_COMBO: usize = 5; // Every this many stars in a combo punch the camera in.
+pub const COMBO_ZOOM: f32 = 0.04;
+pub const MAX_COMBO_ZOOM: f32 = 0.12;
+
+// Shakes, stops and zooms the camera on the CameraEffects sent by the gameplay.
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraShake>()
+            .add_event::<CameraEffect>()
+            // The game stands still during a hit-stop
+            .add_system(tick_hit_stop.before(run_simulation))
+            .add_system(receive_camera_effects.after(run_simulation))
+            .add_system(update_camera.after(receive_camera_effects))
+            // Upon leaving the game state, settle the camera down
+            .add_system(reset_camera_shake.in_schedule(OnExit(AppState::Game)));
+    }
+}
+
+// Run condition keeping the simulation stopped during a hit-stop.
+pub fn not_in_hit_stop(camera_shake: Res<CameraShake>) -> bool {
+    camera_shake.hit_stop <= 0.0
+}
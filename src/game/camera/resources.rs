@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+// What the camera is going through right now.
+#[derive(Resource, Default)]
+pub struct CameraShake {
+    // From 0 to 1, the camera shakes with its square so small hits stay subtle.
+    pub trauma: f32,
+    // Fraction the camera is zoomed in by.
+    pub zoom: f32,
+    // Seconds the game stays stopped for.
+    pub hit_stop: f32,
+    // Drives the shake, so it keeps moving smoothly.
+    pub time: f32,
+}
@@ -0,0 +1,70 @@
+use super::resources::*;
+use super::{MAX_SHAKE_ANGLE, MAX_SHAKE_OFFSET, SHAKE_FREQUENCY, TRAUMA_DECAY, ZOOM_DECAY};
+use crate::events::CameraEffect;
+use crate::game::{ARENA_HEIGHT, ARENA_WIDTH};
+use crate::settings::resources::Settings;
+
+use bevy::prelude::*;
+
+pub fn tick_hit_stop(mut camera_shake: ResMut<CameraShake>, time: Res<Time>) {
+    camera_shake.hit_stop = (camera_shake.hit_stop - time.delta_seconds()).max(0.0);
+}
+
+// Adds the requested effects, toned down or turned off by the screen shake setting.
+// Full strength without any settings, like in the headless apps.
+pub fn receive_camera_effects(
+    mut camera_effect_event_reader: EventReader<CameraEffect>,
+    mut camera_shake: ResMut<CameraShake>,
+    settings: Option<Res<Settings>>,
+) {
+    let intensity = settings.map_or(1.0, |settings| settings.screen_shake);
+
+    for event in camera_effect_event_reader.iter() {
+        match *event {
+            CameraEffect::Shake(trauma) => {
+                camera_shake.trauma = (camera_shake.trauma + trauma * intensity).min(1.0);
+            }
+            CameraEffect::HitStop(duration) => {
+                camera_shake.hit_stop = camera_shake.hit_stop.max(duration * intensity);
+            }
+            CameraEffect::ZoomPunch(zoom) => {
+                camera_shake.zoom = camera_shake.zoom.max(zoom * intensity);
+            }
+        }
+    }
+}
+
+// Places the camera at the arena center, offset by the shake and zoomed by the punches.
+pub fn update_camera(
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<Camera>>,
+    mut camera_shake: ResMut<CameraShake>,
+    time: Res<Time>,
+) {
+    let delta_seconds = time.delta_seconds();
+
+    camera_shake.time += delta_seconds;
+    let shake = camera_shake.trauma * camera_shake.trauma;
+    // Sines at unrelated frequencies wander around smoothly without repeating too soon.
+    let t = camera_shake.time * SHAKE_FREQUENCY;
+    let offset = Vec2::new(
+        (t * 1.0).sin() + (t * 2.3 + 1.7).sin() * 0.5,
+        (t * 1.3 + 4.1).sin() + (t * 2.9 + 0.3).sin() * 0.5,
+    ) / 1.5
+        * MAX_SHAKE_OFFSET
+        * shake;
+    let angle = (t * 1.7 + 2.5).sin() * MAX_SHAKE_ANGLE * shake;
+
+    for (mut camera_transform, mut projection) in camera_query.iter_mut() {
+        camera_transform.translation.x = ARENA_WIDTH / 2.0 + offset.x;
+        camera_transform.translation.y = ARENA_HEIGHT / 2.0 + offset.y;
+        camera_transform.rotation = Quat::from_rotation_z(angle);
+        projection.scale = 1.0 - camera_shake.zoom;
+    }
+
+    camera_shake.trauma = (camera_shake.trauma - TRAUMA_DECAY * delta_seconds).max(0.0);
+    camera_shake.zoom *= (1.0 - ZOOM_DECAY * delta_seconds).max(0.0);
+}
+
+pub fn reset_camera_shake(mut camera_shake: ResMut<CameraShake>) {
+    *camera_shake = CameraShake::default();
+}
@@ -0,0 +1,67 @@
+pub mod resources;
+mod systems;
+
+use super::replay::resources::ReplayPlayback;
+use super::simulation::SimulationSchedule;
+use super::GameMode;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const DAILY_SCORES_PATH: &str = "daily_scores.ron";
+pub const MUTATORS: [Mutator; 4] = [
+    Mutator::StarRush,
+    Mutator::StarDrought,
+    Mutator::Swarm,
+    Mutator::OneLife,
+];
+
+pub struct DailyPlugin;
+
+impl Plugin for DailyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DailyCalendar>()
+            .init_resource::<DailyScores>()
+            .add_startup_system(load_daily_scores)
+            // The day's seed has to be set before the game state spawns anything.
+            // A replay of a daily run already comes with its own.
+            .add_system(
+                start_daily_attempt
+                    .run_if(in_daily_mode)
+                    .run_if(not(resource_exists::<ReplayPlayback>()))
+                    .in_schedule(OnExit(AppState::Menu)),
+            )
+            // Only for a restart, going back to the menu doesn't start an attempt.
+            // The state already holds the one being entered by the time OnExit runs.
+            .add_system(
+                start_daily_attempt
+                    .run_if(in_daily_mode)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(not(resource_exists::<ReplayPlayback>()))
+                    .in_schedule(OnExit(AppState::GameOver)),
+            )
+            .add_system(
+                apply_mutator
+                    .run_if(in_daily_mode)
+                    .in_schedule(OnEnter(AppState::Game)),
+            )
+            .add_system(
+                limit_lives
+                    .run_if(in_daily_mode)
+                    .in_schedule(SimulationSchedule),
+            )
+            .add_system(
+                record_daily_score
+                    .run_if(in_daily_mode)
+                    .run_if(resource_exists::<DailyAttempt>())
+                    .run_if(not(resource_exists::<ReplayPlayback>())),
+            )
+            .add_system(print_daily_scores);
+    }
+}
+
+pub fn in_daily_mode(game_mode: Res<GameMode>) -> bool {
+    *game_mode == GameMode::Daily
+}
@@ -0,0 +1,94 @@
+use super::MUTATORS;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Extra rule of the day, the same one for everybody playing that day.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Mutator {
+    // Stars show up twice as often.
+    StarRush,
+    // Stars show up half as often.
+    StarDrought,
+    // Enemies come in twice as often.
+    Swarm,
+    // A single hit ends the run.
+    OneLife,
+}
+
+impl Mutator {
+    // The mutators take turns, one per day.
+    pub fn for_day(day: i64) -> Self {
+        MUTATORS[day.rem_euclid(MUTATORS.len() as i64) as usize]
+    }
+
+    // Daily seeds carry their day's mutator, so replays of daily runs get the right one.
+    pub fn from_seed(seed: u64) -> Self {
+        MUTATORS[(seed % MUTATORS.len() as u64) as usize]
+    }
+}
+
+// Seed of the given day's challenge.
+pub fn daily_seed(day: i64) -> u64 {
+    // SplitMix64 spreads neighbouring days far apart.
+    let mut mixed = (day as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    mixed ^= mixed >> 31;
+
+    let mutators_num = MUTATORS.len() as u64;
+    let mutator_index = day.rem_euclid(mutators_num as i64) as u64;
+    mixed - mixed % mutators_num + mutator_index
+}
+
+// Tells which day it is. The date can be fixed, e.g. to check that a day's challenge
+// always looks the same.
+#[derive(Resource, Default)]
+pub struct DailyCalendar {
+    pub fixed_day: Option<i64>,
+}
+
+impl DailyCalendar {
+    // Days since the Unix epoch according to the system clock, no network needed.
+    // Counted in UTC, so everybody switches to the next challenge at the same moment.
+    pub fn today(&self) -> i64 {
+        if let Some(day) = self.fixed_day {
+            return day;
+        }
+
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        (seconds / 86_400) as i64
+    }
+}
+
+// The daily run being played.
+#[derive(Resource)]
+pub struct DailyAttempt {
+    pub day: i64,
+    // Only the first attempt of the day counts.
+    pub scored: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DailyScore {
+    pub day: i64,
+    pub score: usize,
+}
+
+// Daily leaderboard, one entry per played day. Kept apart from the high scores.
+#[derive(Resource, Serialize, Deserialize, Debug, Default)]
+pub struct DailyScores {
+    pub scores: Vec<DailyScore>,
+}
+
+impl DailyScores {
+    pub fn get_mut(&mut self, day: i64) -> Option<&mut DailyScore> {
+        self.scores
+            .iter_mut()
+            .find(|daily_score| daily_score.day == day)
+    }
+}
@@ -0,0 +1,124 @@
+use super::resources::*;
+use super::DAILY_SCORES_PATH;
+use crate::events::GameOver;
+use crate::game::enemy::resources::EnemyTimer;
+use crate::game::player::components::{Lives, Player};
+use crate::game::simulation::resources::GameRng;
+use crate::game::star::resources::StarTimer;
+
+use bevy::prelude::*;
+use std::cmp::Reverse;
+
+// Reads the daily leaderboard, there's none before the first daily run.
+pub fn load_daily_scores(mut daily_scores: ResMut<DailyScores>) {
+    let scores = match std::fs::read_to_string(DAILY_SCORES_PATH) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    match ron::from_str::<DailyScores>(&scores) {
+        Ok(v) => *daily_scores = v,
+        Err(error) => println!("Could not parse {}: {}", DAILY_SCORES_PATH, error),
+    }
+}
+
+fn save_daily_scores(daily_scores: &DailyScores) {
+    let scores = match ron::ser::to_string_pretty(daily_scores, default()) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not serialize the daily scores: {}", error);
+            return;
+        }
+    };
+
+    if let Err(error) = std::fs::write(DAILY_SCORES_PATH, scores) {
+        println!("Could not write {}: {}", DAILY_SCORES_PATH, error);
+    }
+}
+
+// Seeds the run with the day's seed. The attempt is taken as soon as the run starts,
+// quitting halfway doesn't give another one.
+pub fn start_daily_attempt(
+    mut commands: Commands,
+    daily_calendar: Res<DailyCalendar>,
+    mut daily_scores: ResMut<DailyScores>,
+) {
+    let day = daily_calendar.today();
+    let seed = daily_seed(day);
+
+    // A fixed date is for checking the challenge, it doesn't use up an attempt.
+    let scored = daily_calendar.fixed_day.is_none() && daily_scores.get_mut(day).is_none();
+    if scored {
+        daily_scores.scores.push(DailyScore { day, score: 0 });
+        save_daily_scores(&daily_scores);
+    } else {
+        println!("Today's challenge was already played, this run won't be scored!");
+    }
+
+    println!(
+        "Daily challenge of day {}: {:?}",
+        day,
+        Mutator::from_seed(seed)
+    );
+
+    commands.insert_resource(GameRng::new(seed));
+    commands.insert_resource(DailyAttempt { day, scored });
+}
+
+pub fn apply_mutator(
+    game_rng: Res<GameRng>,
+    mut enemy_timer: ResMut<EnemyTimer>,
+    mut star_timer: ResMut<StarTimer>,
+) {
+    let star_period = star_timer.timer.duration();
+    let enemy_period = enemy_timer.timer.duration();
+
+    match Mutator::from_seed(game_rng.seed) {
+        Mutator::StarRush => star_timer.timer.set_duration(star_period / 2),
+        Mutator::StarDrought => star_timer.timer.set_duration(star_period * 2),
+        Mutator::Swarm => enemy_timer.timer.set_duration(enemy_period / 2),
+        Mutator::OneLife => {}
+    }
+}
+
+// Takes all but one life from the players as they spawn.
+pub fn limit_lives(mut player_query: Query<&mut Lives, Added<Player>>, game_rng: Res<GameRng>) {
+    if Mutator::from_seed(game_rng.seed) != Mutator::OneLife {
+        return;
+    }
+
+    for mut lives in player_query.iter_mut() {
+        lives.value = 1;
+    }
+}
+
+pub fn record_daily_score(
+    mut commands: Commands,
+    mut game_over_event_reader: EventReader<GameOver>,
+    daily_attempt: Res<DailyAttempt>,
+    mut daily_scores: ResMut<DailyScores>,
+) {
+    let score_value = match game_over_event_reader.iter().next() {
+        Some(event) => event.score_value,
+        None => return,
+    };
+
+    if daily_attempt.scored {
+        if let Some(daily_score) = daily_scores.get_mut(daily_attempt.day) {
+            daily_score.score = score_value;
+        }
+        save_daily_scores(&daily_scores);
+    }
+    commands.remove_resource::<DailyAttempt>();
+}
+
+// Prints the best daily runs whenever the leaderboard changes.
+pub fn print_daily_scores(daily_scores: Res<DailyScores>) {
+    if !daily_scores.is_changed() {
+        return;
+    }
+
+    let mut scores = daily_scores.scores.clone();
+    scores.sort_by_key(|daily_score| Reverse(daily_score.score));
+    println!("Daily scores: {:?}", scores);
+}
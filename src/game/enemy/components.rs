@@ -1,6 +1,6 @@
 use ::bevy::prelude::*;
 
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct Enemy {
     pub direction: Vec3,
 }
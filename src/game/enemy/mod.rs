@@ -1,12 +1,15 @@
 pub mod components;
-mod resources;
+pub mod resources;
 mod systems;
 
-use super::GameState;
+use super::simulation::{RngSet, SimulationSchedule};
+use crate::events::SpawnEnemy;
 use crate::AppState;
 use resources::*;
 use systems::*;
 
+pub use systems::enemy_bundle;
+
 use bevy::prelude::*;
 
 pub const ENEMIES_NUM: usize = 4;
@@ -19,20 +22,30 @@ pub struct EnemyPlugin;
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EnemyTimer>()
+            .init_resource::<Wave>()
+            .add_event::<SpawnEnemy>()
             // Upon entering the game state, spawn enemies
-            .add_system(spawn_enemies.in_schedule(OnEnter(AppState::Game)))
-            // Upon leaving the game state, despawn enemies
+            .add_system(
+                spawn_enemies
+                    .in_set(RngSet::Enemies)
+                    .in_schedule(OnEnter(AppState::Game)),
+            )
+            // Upon leaving the game state, despawn enemies and start over from the first wave
             .add_system(despawn_enemies.in_schedule(OnExit(AppState::Game)))
+            .add_system(reset_wave.in_schedule(OnExit(AppState::Game)))
+            .add_system(reset_enemy_timer.in_schedule(OnExit(AppState::Game)))
             .add_systems(
                 (
                     move_enemy,
                     update_enemy_direction.after(move_enemy),
                     limit_enemy_movement.after(update_enemy_direction),
                     enemy_timer_tick,
-                    spawn_enemies_over_time,
+                    spawn_enemies_over_time
+                        .after(enemy_timer_tick)
+                        .in_set(RngSet::Enemies),
+                    spawn_enemy.after(spawn_enemies_over_time),
                 )
-                    .in_set(OnUpdate(AppState::Game))
-                    .in_set(OnUpdate(GameState::Running)),
+                    .in_schedule(SimulationSchedule),
             );
     }
 }
@@ -14,3 +14,9 @@ impl Default for EnemyTimer {
         }
     }
 }
+
+// Number of the current enemy wave, a new wave starts with every spawned enemy.
+#[derive(Resource, Default)]
+pub struct Wave {
+    pub number: usize,
+}
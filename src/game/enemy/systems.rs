@@ -1,10 +1,12 @@
 use super::components::*;
 use super::resources::*;
 use super::{ENEMIES_NUM, ENEMY_SPEED};
+use crate::events::{ParticleBurst, ParticleEffect, Sound, SoundEvent, SpawnEnemy};
 use crate::game::player::PLAYER_SIZE;
+use crate::game::simulation::resources::GameRng;
+use crate::game::{ARENA_HEIGHT, ARENA_WIDTH};
 
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 use rand::prelude::*;
 
 pub fn move_enemy(mut enemy_query: Query<(&mut Transform, &Enemy)>, time: Res<Time>) {
@@ -15,17 +17,14 @@ pub fn move_enemy(mut enemy_query: Query<(&mut Transform, &Enemy)>, time: Res<Ti
 
 pub fn update_enemy_direction(
     mut enemy_query: Query<(&Transform, &mut Enemy)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    mut particle_burst_event_writer: EventWriter<ParticleBurst>,
 ) {
-    let primary_window = window_query.get_single().unwrap();
-
     let enemy_half_size = PLAYER_SIZE / 2.0;
     let x_min = 0.0 + enemy_half_size;
-    let x_max = primary_window.width() - enemy_half_size;
+    let x_max = ARENA_WIDTH - enemy_half_size;
     let y_min = 0.0 + enemy_half_size;
-    let y_max = primary_window.height() - enemy_half_size;
+    let y_max = ARENA_HEIGHT - enemy_half_size;
 
     for (transform, mut enemy) in enemy_query.iter_mut() {
         let mut direction_changed = false;
@@ -45,30 +44,22 @@ pub fn update_enemy_direction(
         }
 
         // Play sound if direction did change.
-        let bounce_sound1 = asset_server.load("audio/impact/footstep_concrete_000.ogg");
-        let bounce_sound2 = asset_server.load("audio/impact/footstep_concrete_001.ogg");
-
-        let sound_effect = if random::<f32>() > 0.5 {
-            bounce_sound1
-        } else {
-            bounce_sound2
-        };
-
-        audio.play(sound_effect);
+        sound_event_writer.send(SoundEvent::at(Sound::EnemyBounce, transform.translation));
+        // The dust flies off the wall, the way the enemy goes now.
+        particle_burst_event_writer.send(ParticleBurst::towards(
+            ParticleEffect::EnemyBounce,
+            transform.translation,
+            enemy.direction,
+        ));
     }
 }
 
-pub fn limit_enemy_movement(
-    mut enemy_query: Query<&mut Transform, With<Enemy>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-) {
-    let primary_window = window_query.get_single().unwrap();
-
+pub fn limit_enemy_movement(mut enemy_query: Query<&mut Transform, With<Enemy>>) {
     let enemy_half_size = PLAYER_SIZE / 2.0;
     let x_min = 0.0 + enemy_half_size;
-    let x_max = primary_window.width() - enemy_half_size;
+    let x_max = ARENA_WIDTH - enemy_half_size;
     let y_min = 0.0 + enemy_half_size;
-    let y_max = primary_window.height() - enemy_half_size;
+    let y_max = ARENA_HEIGHT - enemy_half_size;
 
     for mut transform in enemy_query.iter_mut() {
         let mut enemy_translation = transform.translation;
@@ -89,60 +80,82 @@ pub fn limit_enemy_movement(
     }
 }
 
+// Spawned right away rather than through SpawnEnemy events. Those are only read on
+// a simulation tick, and frames too short for a tick would let them expire unread.
 pub fn spawn_enemies(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut particle_burst_event_writer: EventWriter<ParticleBurst>,
     asset_server: Res<AssetServer>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let primary_window = window_query.get_single().unwrap();
-
     for _ in 0..ENEMIES_NUM {
-        let x_pos: f32 = random::<f32>() * primary_window.width();
-        let y_pos: f32 = random::<f32>() * primary_window.height();
-
-        commands.spawn((
-            SpriteBundle {
-                transform: Transform::from_xyz(x_pos, y_pos, 0.0),
-                texture: asset_server.load("sprites/Default/ball_red_large.png"),
-                ..default()
-            },
-            Enemy {
-                direction: Vec3::new(random::<f32>(), random::<f32>(), 0.0).normalize(),
-            },
-        ));
+        let x_pos: f32 = game_rng.rng.gen::<f32>() * ARENA_WIDTH;
+        let y_pos: f32 = game_rng.rng.gen::<f32>() * ARENA_HEIGHT;
+        let position = Vec3::new(x_pos, y_pos, 0.0);
+        let direction =
+            Vec3::new(game_rng.rng.gen::<f32>(), game_rng.rng.gen::<f32>(), 0.0).normalize();
+
+        commands.spawn(enemy_bundle(position, direction, &asset_server));
+        particle_burst_event_writer.send(ParticleBurst::at(ParticleEffect::EnemySpawned, position));
     }
 }
 
 // Spawn enemy at random position with random direction over ENEMY_SPAWN_PERIOD time.
+// Every spawn starts a new wave.
 pub fn spawn_enemies_over_time(
-    mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
+    mut spawn_enemy_event_writer: EventWriter<SpawnEnemy>,
     enemy_timer: Res<EnemyTimer>,
+    mut wave: ResMut<Wave>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     if enemy_timer.timer.just_finished() {
-        let primary_window = window_query.get_single().unwrap();
-
         // Generate random position and random direction
-        let x_pos: f32 = random::<f32>() * primary_window.width();
-        let y_pos: f32 = random::<f32>() * primary_window.height();
-
-        let x_dir: f32 = random::<f32>();
-        let y_dir: f32 = random::<f32>();
-
-        commands.spawn((
-            SpriteBundle {
-                transform: Transform::from_xyz(x_pos, y_pos, 0.0),
-                texture: asset_server.load("sprites/Default/ball_red_large.png"),
-                ..default()
-            },
-            Enemy {
-                direction: Vec3::new(x_dir, y_dir, 0.0).normalize(),
-            },
+        let x_pos: f32 = game_rng.rng.gen::<f32>() * ARENA_WIDTH;
+        let y_pos: f32 = game_rng.rng.gen::<f32>() * ARENA_HEIGHT;
+
+        let x_dir: f32 = game_rng.rng.gen::<f32>();
+        let y_dir: f32 = game_rng.rng.gen::<f32>();
+
+        spawn_enemy_event_writer.send(SpawnEnemy {
+            position: Vec3::new(x_pos, y_pos, 0.0),
+            direction: Vec3::new(x_dir, y_dir, 0.0).normalize(),
+        });
+
+        wave.number += 1;
+    }
+}
+
+// Spawns the enemies requested by the SpawnEnemy events.
+pub fn spawn_enemy(
+    mut commands: Commands,
+    mut spawn_enemy_event_reader: EventReader<SpawnEnemy>,
+    mut particle_burst_event_writer: EventWriter<ParticleBurst>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in spawn_enemy_event_reader.iter() {
+        commands.spawn(enemy_bundle(event.position, event.direction, &asset_server));
+        particle_burst_event_writer.send(ParticleBurst::at(
+            ParticleEffect::EnemySpawned,
+            event.position,
         ));
     }
 }
 
+pub fn enemy_bundle(
+    position: Vec3,
+    direction: Vec3,
+    asset_server: &AssetServer,
+) -> (SpriteBundle, Enemy) {
+    (
+        SpriteBundle {
+            transform: Transform::from_translation(position),
+            texture: asset_server.load("sprites/Default/ball_red_large.png"),
+            ..default()
+        },
+        Enemy { direction },
+    )
+}
+
 pub fn despawn_enemies(mut commands: Commands, enemy_query: Query<Entity, With<Enemy>>) {
     for enemy in enemy_query.iter() {
         commands.entity(enemy).despawn();
@@ -152,3 +165,11 @@ pub fn despawn_enemies(mut commands: Commands, enemy_query: Query<Entity, With<E
 pub fn enemy_timer_tick(mut enemy_timer: ResMut<EnemyTimer>, time: Res<Time>) {
     enemy_timer.timer.tick(time.delta());
 }
+
+pub fn reset_wave(mut wave: ResMut<Wave>) {
+    wave.number = 0;
+}
+
+pub fn reset_enemy_timer(mut enemy_timer: ResMut<EnemyTimer>) {
+    *enemy_timer = EnemyTimer::default();
+}
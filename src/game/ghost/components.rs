@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// Translucent ball following the best run, it doesn't interact with anything.
+#[derive(Component)]
+pub struct Ghost {}
@@ -0,0 +1,33 @@
+pub mod components;
+pub mod resources;
+mod systems;
+
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const GHOST_ALPHA: f32 = 0.35;
+
+pub struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // The run's seed has to be picked before the game state spawns anything
+            .add_system(prepare_ghost.in_schedule(OnExit(AppState::Menu)))
+            .add_system(prepare_ghost.in_schedule(OnExit(AppState::GameOver)))
+            .add_system(
+                spawn_ghost
+                    .run_if(resource_exists::<GhostRun>())
+                    .in_schedule(OnEnter(AppState::Game)),
+            )
+            .add_system(despawn_ghost.in_schedule(OnExit(AppState::Game)))
+            .add_system(
+                move_ghost
+                    .run_if(resource_exists::<GhostRun>())
+                    .in_set(OnUpdate(AppState::Game)),
+            );
+    }
+}
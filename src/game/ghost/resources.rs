@@ -0,0 +1,23 @@
+use crate::game::replay::resources::TrackPoint;
+
+use bevy::prelude::*;
+
+// The best run being raced, only present while racing a ghost.
+#[derive(Resource)]
+pub struct GhostRun {
+    pub track: Vec<TrackPoint>,
+    pub score: usize,
+}
+
+impl GhostRun {
+    pub fn point_at(&self, tick: u64) -> Option<&TrackPoint> {
+        self.track.get(tick as usize)
+    }
+
+    // Score the best run had on the given tick, its final score once it's over.
+    pub fn score_at(&self, tick: u64) -> usize {
+        self.point_at(tick)
+            .map(|point| point.score)
+            .unwrap_or(self.score)
+    }
+}
@@ -0,0 +1,108 @@
+use super::components::*;
+use super::resources::*;
+use super::GHOST_ALPHA;
+use crate::game::player::resources::PlayerCount;
+use crate::game::replay::resources::Replay;
+use crate::game::save::resources::ResumedGame;
+use crate::game::score::resources::HighScores;
+use crate::game::simulation::resources::{GameRng, SimulationClock};
+use crate::game::GameMode;
+use crate::settings::resources::Settings;
+
+use bevy::prelude::*;
+
+// Sets up a race against the best solo run of the selected mode. The run gets the same seed,
+// so the ghost moves through the same layout.
+pub fn prepare_ghost(
+    mut commands: Commands,
+    settings: Option<Res<Settings>>,
+    high_scores: Res<HighScores>,
+    game_mode: Res<GameMode>,
+    player_count: Res<PlayerCount>,
+    simulation_clock: Res<SimulationClock>,
+    resumed_game: Option<Res<ResumedGame>>,
+) {
+    commands.remove_resource::<GhostRun>();
+
+    // On by default, like in the headless apps without any settings.
+    let ghost_enabled = match settings {
+        Some(v) => v.ghost,
+        None => true,
+    };
+
+    // Replays, rollback sessions, the daily challenge and continued runs bring their own seed.
+    if !ghost_enabled
+        || simulation_clock.manual
+        || resumed_game.is_some()
+        || player_count.value != 1
+        || *game_mode == GameMode::Daily
+    {
+        return;
+    }
+
+    let high_score = match high_scores.best_of(*game_mode, player_count.value) {
+        Some(v) => v,
+        None => return,
+    };
+    let replay = match Replay::load(high_score.seed, high_score.score) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    println!(
+        "Racing the ghost of a run worth {} points",
+        high_score.score
+    );
+
+    commands.insert_resource(GameRng::new(replay.seed));
+    commands.insert_resource(GhostRun {
+        track: replay.track,
+        score: high_score.score,
+    });
+}
+
+pub fn spawn_ghost(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(1.0, 1.0, 1.0, GHOST_ALPHA),
+                ..default()
+            },
+            // Drawn under the live players.
+            transform: Transform::from_xyz(0.0, 0.0, -1.0),
+            texture: asset_server.load("sprites/Default/ball_blue_large_alt.png"),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        Ghost {},
+    ));
+}
+
+pub fn despawn_ghost(mut commands: Commands, ghost_query: Query<Entity, With<Ghost>>) {
+    for ghost_entity in ghost_query.iter() {
+        commands.entity(ghost_entity).despawn();
+    }
+}
+
+// Puts the ghost where the best run's player was on the current tick.
+pub fn move_ghost(
+    mut ghost_query: Query<(&mut Transform, &mut Visibility), With<Ghost>>,
+    ghost_run: Res<GhostRun>,
+    simulation_clock: Res<SimulationClock>,
+) {
+    let (mut ghost_transform, mut ghost_visibility) = match ghost_query.get_single_mut() {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    match ghost_run
+        .point_at(simulation_clock.tick)
+        .and_then(|point| point.position)
+    {
+        Some(position) => {
+            ghost_transform.translation = Vec2::from(position).extend(-1.0);
+            *ghost_visibility = Visibility::Inherited;
+        }
+        None => *ghost_visibility = Visibility::Hidden,
+    }
+}
@@ -1,29 +1,94 @@
-mod enemy;
-mod player;
-mod score;
-mod star;
+pub mod achievement;
+pub mod audio;
+pub mod boss;
+pub mod camera;
+pub mod daily;
+pub mod enemy;
+pub mod ghost;
+pub mod music;
+pub mod objective;
+pub mod particle;
+pub mod player;
+pub mod projectile;
+pub mod replay;
+pub mod save;
+pub mod score;
+pub mod simulation;
+pub mod star;
+pub mod stats;
+pub mod surface;
 mod systems;
+pub mod theme;
+pub mod versus;
 
-use crate::{events::GameOver, AppState};
+use crate::events::{GameOver, StarCollected};
+use crate::AppState;
+use achievement::AchievementPlugin;
+use audio::SoundPlugin;
+use boss::BossPlugin;
+use camera::CameraPlugin;
+use daily::DailyPlugin;
 use enemy::EnemyPlugin;
+use ghost::GhostPlugin;
+use music::MusicPlugin;
+use objective::ObjectivePlugin;
+use particle::ParticlePlugin;
 use player::PlayerPlugin;
+use player::MAX_PLAYERS;
+use projectile::ProjectilePlugin;
+use replay::ReplayPlugin;
+use save::SavePlugin;
 use score::ScorePlugin;
+use simulation::SimulationPlugin;
 use star::StarPlugin;
+use stats::StatsPlugin;
+use surface::SurfacePlugin;
 use systems::*;
+use theme::ThemePlugin;
+use versus::VersusPlugin;
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+// Size of the area the game is played in. It doesn't depend on the window, the camera
+// scales it to fit instead, so a seed lays out the same at every resolution.
+pub const ARENA_WIDTH: f32 = 1280.0;
+pub const ARENA_HEIGHT: f32 = 720.0;
 
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.add_state::<GameState>()
+            .init_resource::<GameMode>()
             .add_event::<GameOver>()
+            .add_event::<StarCollected>()
+            .add_plugin(AchievementPlugin)
+            .add_plugin(SoundPlugin)
+            .add_plugin(BossPlugin)
+            .add_plugin(CameraPlugin)
+            .add_plugin(DailyPlugin)
             .add_plugin(EnemyPlugin)
+            .add_plugin(GhostPlugin)
+            .add_plugin(MusicPlugin)
+            .add_plugin(ObjectivePlugin)
+            .add_plugin(ParticlePlugin)
             .add_plugin(PlayerPlugin)
+            .add_plugin(ProjectilePlugin)
+            .add_plugin(ReplayPlugin)
+            .add_plugin(SavePlugin)
             .add_plugin(ScorePlugin)
+            .add_plugin(SimulationPlugin)
             .add_plugin(StarPlugin)
-            .add_system(toggle_game.run_if(in_state(AppState::Game)));
+            .add_plugin(StatsPlugin)
+            .add_plugin(SurfacePlugin)
+            .add_plugin(ThemePlugin)
+            .add_plugin(VersusPlugin)
+            // Every run starts unpaused, the enemies requested on entering the game would get lost otherwise
+            .add_system(resume_game.in_schedule(OnEnter(AppState::Game)))
+            .add_system(toggle_game.run_if(in_state(AppState::Game)))
+            .add_system(select_game_mode.run_if(in_state(AppState::Menu)));
     }
 }
 
@@ -33,3 +98,50 @@ pub enum GameState {
     #[default]
     Running,
 }
+
+// Rules the next run is played with. Chosen in the menu before entering the game.
+#[derive(Resource, Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum GameMode {
+    // Dodge the enemies and collect as many stars as possible.
+    #[default]
+    Classic,
+    // Collect every key to unlock the exit and advance to the next level.
+    Puzzle,
+    // Classic rules, but the player can shoot the enemies down.
+    Shooter,
+    // Players race each other for the stars, enemy hits cost points instead of lives.
+    Versus,
+    // Classic rules on the day's seed with the day's mutator, one scored attempt per day.
+    Daily,
+}
+
+impl GameMode {
+    pub fn shooting_enabled(&self) -> bool {
+        *self == GameMode::Shooter
+    }
+
+    // How many players can take part in a run of the mode.
+    pub fn player_range(&self) -> RangeInclusive<usize> {
+        match self {
+            // There's nobody to race against alone.
+            GameMode::Versus => 2..=MAX_PLAYERS,
+            // Everybody gets the same daily challenge to beat alone.
+            GameMode::Daily => 1..=1,
+            _ => 1..=MAX_PLAYERS,
+        }
+    }
+}
+
+// Run condition for systems that only belong to the puzzle mode.
+pub fn in_puzzle_mode(game_mode: Res<GameMode>) -> bool {
+    *game_mode == GameMode::Puzzle
+}
+
+// Run condition for the shooting mechanic.
+pub fn shooting_enabled(game_mode: Res<GameMode>) -> bool {
+    game_mode.shooting_enabled()
+}
+
+pub fn in_versus_mode(game_mode: Res<GameMode>) -> bool {
+    *game_mode == GameMode::Versus
+}
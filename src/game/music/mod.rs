@@ -0,0 +1,23 @@
+pub mod resources;
+mod systems;
+
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const MUSIC_CONFIG_PATH: &str = "assets/config/music.ron";
+pub const LAYER_FADE_DURATION: f32 = 2.0; // How long an intensity layer takes to fade in or out.
+pub const DUCK_DURATION: f32 = 0.3;
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MusicConfig>()
+            .init_resource::<MusicPlayer>()
+            .add_startup_system(load_music_config)
+            .add_system(switch_track)
+            .add_system(update_music_volume.after(switch_track));
+    }
+}
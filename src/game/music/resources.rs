@@ -0,0 +1,67 @@
+use crate::AppState;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LayerDefinition {
+    pub path: String,
+    #[serde(default = "full_volume")]
+    pub volume: f32,
+    // Enemies needed around for the layer to play.
+    #[serde(default)]
+    pub min_enemies: usize,
+}
+
+fn full_volume() -> f32 {
+    1.0
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TrackDefinition {
+    pub layers: Vec<LayerDefinition>,
+}
+
+// Tracks of every app state, loaded from MUSIC_CONFIG_PATH at startup.
+#[derive(Resource, Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MusicConfig {
+    pub tracks: HashMap<AppState, TrackDefinition>,
+    pub crossfade: f32,
+    pub duck_volume: f32,
+}
+
+impl Default for MusicConfig {
+    fn default() -> Self {
+        MusicConfig {
+            tracks: HashMap::default(),
+            crossfade: 1.5,
+            duck_volume: 0.3,
+        }
+    }
+}
+
+pub struct PlayingLayer {
+    pub sink: Handle<AudioSink>,
+    pub volume: f32,
+    pub min_enemies: usize,
+    // How far the layer faded in, from 0 to 1.
+    pub gain: f32,
+}
+
+pub struct PlayingTrack {
+    pub state: AppState,
+    pub layers: Vec<PlayingLayer>,
+    // How far the whole track faded in, from 0 to 1.
+    pub fade: f32,
+    // Set once another track took over, the track is stopped once it faded out.
+    pub fading_out: bool,
+}
+
+#[derive(Resource, Default)]
+pub struct MusicPlayer {
+    pub tracks: Vec<PlayingTrack>,
+    // Lowers the music while the game is paused, 1 when it isn't.
+    pub duck: f32,
+}
@@ -0,0 +1,159 @@
+use super::resources::*;
+use super::{DUCK_DURATION, LAYER_FADE_DURATION, MUSIC_CONFIG_PATH};
+use crate::game::audio::resources::Bus;
+use crate::game::enemy::components::Enemy;
+use crate::game::GameState;
+use crate::settings::resources::Settings;
+use crate::AppState;
+
+use bevy::prelude::*;
+
+// Reads the music tracks, the game stays silent without them.
+pub fn load_music_config(mut music_config: ResMut<MusicConfig>) {
+    let config = match std::fs::read_to_string(MUSIC_CONFIG_PATH) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not read {}: {}", MUSIC_CONFIG_PATH, error);
+            return;
+        }
+    };
+
+    match ron::from_str::<MusicConfig>(&config) {
+        Ok(v) => *music_config = v,
+        Err(error) => println!("Could not parse {}: {}", MUSIC_CONFIG_PATH, error),
+    }
+}
+
+// Starts the track of the new app state silently and lets the old one fade out.
+pub fn switch_track(
+    mut music_player: ResMut<MusicPlayer>,
+    music_config: Res<MusicConfig>,
+    app_state: Res<State<AppState>>,
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    audio_sinks: Res<Assets<AudioSink>>,
+) {
+    let state = app_state.0;
+    if music_player
+        .tracks
+        .iter()
+        .any(|track| track.state == state && !track.fading_out)
+    {
+        return;
+    }
+
+    for track in music_player.tracks.iter_mut() {
+        track.fading_out = true;
+    }
+
+    let definition = match music_config.tracks.get(&state) {
+        Some(v) => v,
+        None => return,
+    };
+    // The track is picked up again if it was still fading out, instead of starting over.
+    if let Some(track) = music_player
+        .tracks
+        .iter_mut()
+        .find(|track| track.state == state)
+    {
+        track.fading_out = false;
+        return;
+    }
+
+    let layers = definition
+        .layers
+        .iter()
+        .map(|layer| {
+            let sink = audio.play_with_settings(
+                asset_server.load(layer.path.as_str()),
+                PlaybackSettings::LOOP.with_volume(0.0),
+            );
+            PlayingLayer {
+                // A weak handle can't control the playback.
+                sink: audio_sinks.get_handle(sink),
+                volume: layer.volume,
+                min_enemies: layer.min_enemies,
+                gain: 0.0,
+            }
+        })
+        .collect();
+
+    music_player.tracks.push(PlayingTrack {
+        state,
+        layers,
+        fade: 0.0,
+        fading_out: false,
+    });
+}
+
+// Crossfades the tracks, ducks the music while paused and fades the intensity layers in and
+// out with the number of enemies.
+#[allow(clippy::too_many_arguments)]
+pub fn update_music_volume(
+    mut music_player: ResMut<MusicPlayer>,
+    music_config: Res<MusicConfig>,
+    enemy_query: Query<(), With<Enemy>>,
+    app_state: Res<State<AppState>>,
+    game_state: Res<State<GameState>>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    settings: Option<Res<Settings>>,
+    time: Res<Time>,
+) {
+    let delta_seconds = time.delta_seconds();
+    let enemies_num = enemy_query.iter().count();
+    let bus_volume = Bus::Music.volume(settings.as_deref());
+
+    let paused = app_state.0 == AppState::Game && game_state.0 == GameState::Paused;
+    let duck_target = if paused {
+        music_config.duck_volume
+    } else {
+        1.0
+    };
+    music_player.duck = approach(
+        music_player.duck,
+        duck_target,
+        delta_seconds / DUCK_DURATION,
+    );
+    let duck = music_player.duck;
+
+    let crossfade_step = delta_seconds / music_config.crossfade.max(f32::EPSILON);
+    for track in music_player.tracks.iter_mut() {
+        let fade_target = if track.fading_out { 0.0 } else { 1.0 };
+        track.fade = approach(track.fade, fade_target, crossfade_step);
+
+        for layer in track.layers.iter_mut() {
+            let gain_target = if enemies_num >= layer.min_enemies {
+                1.0
+            } else {
+                0.0
+            };
+            layer.gain = approach(layer.gain, gain_target, delta_seconds / LAYER_FADE_DURATION);
+
+            if let Some(sink) = audio_sinks.get(&layer.sink) {
+                sink.set_volume(bus_volume * duck * track.fade * layer.gain * layer.volume);
+            }
+        }
+    }
+
+    // Looping sinks would keep playing after being dropped, they're stopped first.
+    music_player.tracks.retain(|track| {
+        let faded_out = track.fading_out && track.fade <= 0.0;
+        if faded_out {
+            for layer in track.layers.iter() {
+                if let Some(sink) = audio_sinks.get(&layer.sink) {
+                    sink.stop();
+                }
+            }
+        }
+        !faded_out
+    });
+}
+
+// Moves the value towards the target by at most the given step.
+fn approach(value: f32, target: f32, step: f32) -> f32 {
+    if value < target {
+        (value + step).min(target)
+    } else {
+        (value - step).max(target)
+    }
+}
@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct Key {}
+
+// Obstacle that blocks the player until a key unlocks it.
+#[derive(Component)]
+pub struct Lock {
+    pub size: Vec2,
+}
+
+// Hole the player has to reach to finish the level, once every key is collected.
+#[derive(Component)]
+pub struct Exit {
+    pub locked: bool,
+}
+
+// Marks everything that belongs to the current level, so it can be cleared at once.
+#[derive(Component)]
+pub struct LevelObject {}
@@ -0,0 +1,45 @@
+pub mod components;
+pub mod resources;
+mod systems;
+
+use super::in_puzzle_mode;
+use super::simulation::{RngSet, SimulationSchedule};
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const KEYS_BASE_NUM: usize = 3; // Keys on the first level, every next level adds one more.
+pub const KEY_SIZE: f32 = 34.0;
+pub const EXIT_SIZE: f32 = 80.0;
+
+pub struct ObjectivePlugin;
+
+impl Plugin for ObjectivePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Level>()
+            .init_resource::<KeyCount>()
+            // Upon entering the game state in puzzle mode, spawn the first level
+            .add_system(
+                spawn_first_level
+                    .run_if(in_puzzle_mode)
+                    .in_set(RngSet::Level)
+                    .in_schedule(OnEnter(AppState::Game)),
+            )
+            // Upon leaving the game state, despawn the level and start over next time
+            .add_system(despawn_level.in_schedule(OnExit(AppState::Game)))
+            .add_system(reset_level.in_schedule(OnExit(AppState::Game)))
+            .add_systems(
+                (
+                    check_key_collision,
+                    check_exit_collision
+                        .after(check_key_collision)
+                        .in_set(RngSet::Level),
+                    block_player_movement,
+                )
+                    .distributive_run_if(in_puzzle_mode)
+                    .in_schedule(SimulationSchedule),
+            );
+    }
+}
@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Default)]
+pub struct Level {
+    pub index: usize,
+}
+
+#[derive(Resource, Default)]
+pub struct KeyCount {
+    pub total: usize,
+    pub remaining: usize,
+}
@@ -0,0 +1,290 @@
+use super::components::*;
+use super::resources::*;
+use super::{EXIT_SIZE, KEYS_BASE_NUM, KEY_SIZE};
+use crate::events::{Sound, SoundEvent};
+use crate::game::player::components::Player;
+use crate::game::player::PLAYER_SIZE;
+use crate::game::simulation::resources::GameRng;
+use crate::game::{ARENA_HEIGHT, ARENA_WIDTH};
+
+use bevy::prelude::*;
+use rand::prelude::*;
+
+// Locked block sprites together with their sizes.
+const LOCK_VARIANTS: [(&str, f32, f32); 4] = [
+    ("sprites/Default/block_locked_large.png", 64.0, 128.0),
+    ("sprites/Default/block_locked_narrow.png", 32.0, 128.0),
+    ("sprites/Default/block_locked_small.png", 32.0, 32.0),
+    ("sprites/Default/block_locked_square.png", 64.0, 64.0),
+];
+
+pub fn spawn_first_level(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    level: Res<Level>,
+    mut key_count: ResMut<KeyCount>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    spawn_level(
+        &mut commands,
+        &asset_server,
+        &level,
+        &mut key_count,
+        &mut game_rng,
+    );
+}
+
+// Spawns the keys, the locked blocks and the locked exit of the given level.
+// Every key but the last one opens a block, the last one opens the exit.
+fn spawn_level(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    level: &Level,
+    key_count: &mut KeyCount,
+    game_rng: &mut GameRng,
+) {
+    let keys_num = KEYS_BASE_NUM + level.index;
+    let mut occupied: Vec<(Vec2, Vec2)> = Vec::new();
+
+    // The exit is placed first, so nothing gets spawned on top of it.
+    let exit_position = free_position(game_rng, Vec2::splat(EXIT_SIZE), &occupied);
+    occupied.push((exit_position, Vec2::splat(EXIT_SIZE)));
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_xyz(exit_position.x, exit_position.y, -0.1),
+            texture: asset_server.load("sprites/Default/hole_large_end_locked.png"),
+            ..default()
+        },
+        Exit { locked: true },
+        LevelObject {},
+    ));
+
+    for _ in 0..keys_num - 1 {
+        let (texture, width, height) =
+            LOCK_VARIANTS[game_rng.rng.gen_range(0..LOCK_VARIANTS.len())];
+        let size = Vec2::new(width, height);
+        let position = free_position(game_rng, size, &occupied);
+        occupied.push((position, size));
+
+        commands.spawn((
+            SpriteBundle {
+                transform: Transform::from_xyz(position.x, position.y, 0.0),
+                texture: asset_server.load(texture),
+                ..default()
+            },
+            Lock { size },
+            LevelObject {},
+        ));
+    }
+
+    for _ in 0..keys_num {
+        let position = free_position(game_rng, Vec2::splat(KEY_SIZE), &occupied);
+        occupied.push((position, Vec2::splat(KEY_SIZE)));
+
+        commands.spawn((
+            SpriteBundle {
+                transform: Transform::from_xyz(position.x, position.y, 0.0),
+                texture: asset_server.load("sprites/Default/key.png"),
+                ..default()
+            },
+            Key {},
+            LevelObject {},
+        ));
+    }
+
+    key_count.total = keys_num;
+    key_count.remaining = keys_num;
+
+    println!(
+        "Level {} started, keys to collect: {}",
+        level.index + 1,
+        keys_num
+    );
+}
+
+// Picks a random position inside the arena that doesn't overlap already placed objects
+// nor the player's spawn point in the center of the arena.
+fn free_position(game_rng: &mut GameRng, size: Vec2, occupied: &[(Vec2, Vec2)]) -> Vec2 {
+    let arena_size = Vec2::new(ARENA_WIDTH, ARENA_HEIGHT);
+    let player_spawn = arena_size / 2.0;
+    let half_size = size / 2.0;
+
+    let mut position = Vec2::ZERO;
+    // Give up after a few attempts, crowded levels are still playable.
+    for _ in 0..32 {
+        position = Vec2::new(
+            half_size.x + game_rng.rng.gen::<f32>() * (arena_size.x - size.x),
+            half_size.y + game_rng.rng.gen::<f32>() * (arena_size.y - size.y),
+        );
+
+        let overlaps_spawn = (position - player_spawn)
+            .abs()
+            .cmplt(half_size + PLAYER_SIZE)
+            .all();
+        let overlaps_object = occupied.iter().any(|(other_position, other_size)| {
+            (position - *other_position)
+                .abs()
+                .cmplt(half_size + *other_size / 2.0)
+                .all()
+        });
+
+        if !overlaps_spawn && !overlaps_object {
+            break;
+        }
+    }
+
+    position
+}
+
+pub fn despawn_level(mut commands: Commands, objective_query: Query<Entity, With<LevelObject>>) {
+    for entity in objective_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn reset_level(mut level: ResMut<Level>, mut key_count: ResMut<KeyCount>) {
+    level.index = 0;
+    key_count.total = 0;
+    key_count.remaining = 0;
+}
+
+// Checks if any player picks up a key. Every key opens one of the locked blocks,
+// the last one opens the exit.
+#[allow(clippy::too_many_arguments)]
+pub fn check_key_collision(
+    mut commands: Commands,
+    player_query: Query<&Transform, With<Player>>,
+    key_query: Query<(&Transform, Entity), With<Key>>,
+    lock_query: Query<Entity, With<Lock>>,
+    mut exit_query: Query<(&mut Handle<Image>, &mut Exit)>,
+    asset_server: Res<AssetServer>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    mut key_count: ResMut<KeyCount>,
+) {
+    let mut locks = lock_query.iter();
+
+    for (key_transform, key_entity) in key_query.iter() {
+        let key_picked_up = player_query.iter().any(|player_transform| {
+            player_transform
+                .translation
+                .distance(key_transform.translation)
+                < PLAYER_SIZE / 2.0 + KEY_SIZE / 2.0
+        });
+        if !key_picked_up {
+            continue;
+        }
+
+        commands.entity(key_entity).despawn();
+        key_count.remaining = key_count.remaining.saturating_sub(1);
+
+        sound_event_writer.send(SoundEvent::at(
+            Sound::KeyCollected,
+            key_transform.translation,
+        ));
+
+        if let Some(lock_entity) = locks.next() {
+            commands.entity(lock_entity).despawn();
+        }
+
+        if key_count.remaining > 0 {
+            continue;
+        }
+
+        // All keys are collected, open the exit.
+        for (mut exit_texture, mut exit) in exit_query.iter_mut() {
+            exit.locked = false;
+            *exit_texture = asset_server.load("sprites/Default/hole_large_end.png");
+        }
+
+        sound_event_writer.send(Sound::ExitUnlocked.into());
+        println!("All keys collected, the exit is open!");
+    }
+}
+
+// Advances to the next level once any player reaches the open exit.
+#[allow(clippy::too_many_arguments)]
+pub fn check_exit_collision(
+    mut commands: Commands,
+    player_query: Query<&Transform, With<Player>>,
+    exit_query: Query<(&Transform, &Exit)>,
+    objective_query: Query<Entity, With<LevelObject>>,
+    asset_server: Res<AssetServer>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    mut level: ResMut<Level>,
+    mut key_count: ResMut<KeyCount>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let exit_reached = exit_query.iter().any(|(exit_transform, exit)| {
+        !exit.locked
+            && player_query.iter().any(|player_transform| {
+                player_transform
+                    .translation
+                    .truncate()
+                    .distance(exit_transform.translation.truncate())
+                    < EXIT_SIZE / 2.0
+            })
+    });
+
+    if !exit_reached {
+        return;
+    }
+
+    println!("Level {} completed!", level.index + 1);
+
+    sound_event_writer.send(Sound::LevelCompleted.into());
+
+    for entity in objective_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    level.index += 1;
+
+    spawn_level(
+        &mut commands,
+        &asset_server,
+        &level,
+        &mut key_count,
+        &mut game_rng,
+    );
+}
+
+// Pushes the players out of the locked blocks.
+pub fn block_player_movement(
+    mut player_query: Query<&mut Transform, With<Player>>,
+    lock_query: Query<(&Transform, &Lock), Without<Player>>,
+) {
+    let player_radius = PLAYER_SIZE / 2.0;
+
+    for mut player_transform in player_query.iter_mut() {
+        for (lock_transform, lock) in lock_query.iter() {
+            let player_position = player_transform.translation.truncate();
+            let lock_position = lock_transform.translation.truncate();
+            let half_size = lock.size / 2.0;
+
+            // Closest point of the block to the player's center.
+            let closest =
+                player_position.clamp(lock_position - half_size, lock_position + half_size);
+            let offset = player_position - closest;
+            let distance = offset.length();
+
+            if distance >= player_radius {
+                continue;
+            }
+
+            let push = if distance > 0.0 {
+                offset / distance * (player_radius - distance)
+            } else {
+                // The player's center is inside the block, push it out through the nearest side.
+                let inside = player_position - lock_position;
+                let depth = half_size - inside.abs();
+                if depth.x < depth.y {
+                    Vec2::new(inside.x.signum() * (depth.x + player_radius), 0.0)
+                } else {
+                    Vec2::new(0.0, inside.y.signum() * (depth.y + player_radius))
+                }
+            };
+
+            player_transform.translation += push.extend(0.0);
+        }
+    }
+}
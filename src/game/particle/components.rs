@@ -0,0 +1,19 @@
+use crate::events::ParticleEffect;
+
+use bevy::prelude::*;
+
+// Emits the particles of a burst, spread over the preset's duration, then goes away.
+#[derive(Component)]
+pub struct ParticleEmitter {
+    pub effect: ParticleEffect,
+    pub direction: Option<Vec3>,
+    pub timer: Timer,
+    pub emitted: usize,
+}
+
+#[derive(Component)]
+pub struct Particle {
+    pub effect: ParticleEffect,
+    pub velocity: Vec3,
+    pub timer: Timer,
+}
@@ -0,0 +1,38 @@
+pub mod components;
+pub mod resources;
+mod systems;
+
+use super::simulation::run_simulation;
+use super::GameState;
+use crate::events::ParticleBurst;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const PARTICLES_CONFIG_PATH: &str = "assets/config/particles.ron";
+pub const PARTICLE_Z: f32 = 0.5; // Above the floor and the players, below the UI.
+
+// Spawns the particle bursts asked for with ParticleBursts. The particles are only for show,
+// they live outside the simulation and stop in place while the game is paused.
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ParticleLibrary>()
+            .add_event::<ParticleBurst>()
+            .add_startup_system(load_particle_library)
+            // The ticks of this frame ask for their bursts while the simulation runs
+            .add_systems(
+                (
+                    spawn_emitters.after(run_simulation),
+                    emit_particles.after(spawn_emitters),
+                    update_particles,
+                )
+                    .in_set(OnUpdate(AppState::Game))
+                    .in_set(OnUpdate(GameState::Running)),
+            )
+            .add_system(despawn_particles.in_schedule(OnExit(AppState::Game)));
+    }
+}
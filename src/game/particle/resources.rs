@@ -0,0 +1,80 @@
+use crate::events::ParticleEffect;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ParticlePreset {
+    // One of the textures is picked for every particle.
+    pub textures: Vec<String>,
+    pub count: usize,
+    // How long the emitter keeps emitting, the whole burst comes out at once with zero.
+    pub duration: f32,
+    pub lifetime: (f32, f32),
+    pub speed: (f32, f32),
+    // Angle around the burst's direction the particles fly within, in degrees.
+    pub spread: f32,
+    // How much of the speed is lost every second.
+    pub damping: f32,
+    // Colors and scales the particles go through over their life, evenly spaced.
+    pub colors: Vec<(f32, f32, f32, f32)>,
+    pub scales: Vec<f32>,
+}
+
+impl Default for ParticlePreset {
+    fn default() -> Self {
+        ParticlePreset {
+            textures: Vec::new(),
+            count: 8,
+            duration: 0.0,
+            lifetime: (0.3, 0.5),
+            speed: (100.0, 300.0),
+            spread: 360.0,
+            damping: 0.0,
+            colors: vec![(1.0, 1.0, 1.0, 1.0), (1.0, 1.0, 1.0, 0.0)],
+            scales: vec![1.0],
+        }
+    }
+}
+
+impl ParticlePreset {
+    // Color of a particle that has lived the given fraction of its life.
+    pub fn color(&self, fraction: f32) -> Color {
+        let (r, g, b, a) = sample(&self.colors, fraction, |a, b, t| {
+            (
+                a.0 + (b.0 - a.0) * t,
+                a.1 + (b.1 - a.1) * t,
+                a.2 + (b.2 - a.2) * t,
+                a.3 + (b.3 - a.3) * t,
+            )
+        })
+        .unwrap_or((1.0, 1.0, 1.0, 1.0));
+        Color::rgba(r, g, b, a)
+    }
+
+    pub fn scale(&self, fraction: f32) -> f32 {
+        sample(&self.scales, fraction, |a, b, t| a + (b - a) * t).unwrap_or(1.0)
+    }
+}
+
+// Blends between the two points of the curve around the fraction.
+fn sample<T: Copy>(curve: &[T], fraction: f32, lerp: impl Fn(T, T, f32) -> T) -> Option<T> {
+    let last = curve.len().checked_sub(1)?;
+    let position = fraction.clamp(0.0, 1.0) * last as f32;
+    let index = (position as usize).min(last);
+    let next = (index + 1).min(last);
+    Some(lerp(curve[index], curve[next], position - index as f32))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ParticlesConfig {
+    pub presets: HashMap<ParticleEffect, ParticlePreset>,
+}
+
+// Every preset with its textures, loaded up front so nothing gets loaded mid-game.
+#[derive(Resource, Default)]
+pub struct ParticleLibrary {
+    pub presets: HashMap<ParticleEffect, (ParticlePreset, Vec<Handle<Image>>)>,
+}
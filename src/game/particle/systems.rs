@@ -0,0 +1,168 @@
+use super::components::*;
+use super::resources::*;
+use super::{PARTICLES_CONFIG_PATH, PARTICLE_Z};
+use crate::events::ParticleBurst;
+
+use bevy::prelude::*;
+use rand::prelude::*;
+use std::f32::consts::TAU;
+
+pub fn load_particle_library(
+    mut particle_library: ResMut<ParticleLibrary>,
+    asset_server: Res<AssetServer>,
+) {
+    let config = match std::fs::read_to_string(PARTICLES_CONFIG_PATH) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not read {}: {}", PARTICLES_CONFIG_PATH, error);
+            return;
+        }
+    };
+    let config = match ron::from_str::<ParticlesConfig>(&config) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not parse {}: {}", PARTICLES_CONFIG_PATH, error);
+            return;
+        }
+    };
+
+    for (effect, preset) in config.presets {
+        let handles = preset
+            .textures
+            .iter()
+            .map(|path| asset_server.load(path.as_str()))
+            .collect();
+        particle_library.presets.insert(effect, (preset, handles));
+    }
+}
+
+pub fn spawn_emitters(
+    mut commands: Commands,
+    mut particle_burst_event_reader: EventReader<ParticleBurst>,
+    particle_library: Res<ParticleLibrary>,
+) {
+    for event in particle_burst_event_reader.iter() {
+        let (preset, _) = match particle_library.presets.get(&event.effect) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        commands.spawn((
+            SpatialBundle::from_transform(Transform::from_translation(event.position)),
+            ParticleEmitter {
+                effect: event.effect,
+                direction: event.direction,
+                timer: Timer::from_seconds(preset.duration, TimerMode::Once),
+                emitted: 0,
+            },
+        ));
+    }
+}
+
+// Lets out the particles due by now, spread evenly over the emitter's duration.
+pub fn emit_particles(
+    mut commands: Commands,
+    mut emitter_query: Query<(Entity, &Transform, &mut ParticleEmitter)>,
+    particle_library: Res<ParticleLibrary>,
+    time: Res<Time>,
+) {
+    let mut rng = thread_rng();
+
+    for (emitter_entity, emitter_transform, mut emitter) in emitter_query.iter_mut() {
+        let (preset, textures) = match particle_library.presets.get(&emitter.effect) {
+            Some(v) => v,
+            None => {
+                commands.entity(emitter_entity).despawn();
+                continue;
+            }
+        };
+
+        emitter.timer.tick(time.delta());
+        let due = if emitter.timer.finished() {
+            preset.count
+        } else {
+            (preset.count as f32 * emitter.timer.percent()).ceil() as usize
+        };
+
+        let base_angle = emitter
+            .direction
+            .map_or(0.0, |direction| direction.y.atan2(direction.x));
+        let spread = preset.spread.to_radians().min(TAU);
+
+        for _ in emitter.emitted..due {
+            let angle = base_angle + rng.gen_range(-0.5..=0.5) * spread;
+            let speed = rng.gen_range(preset.speed.0..=preset.speed.1.max(preset.speed.0));
+            let lifetime =
+                rng.gen_range(preset.lifetime.0..=preset.lifetime.1.max(preset.lifetime.0));
+
+            let mut translation = emitter_transform.translation;
+            translation.z = PARTICLE_Z;
+
+            commands.spawn((
+                SpriteBundle {
+                    transform: Transform::from_translation(translation)
+                        .with_scale(Vec3::splat(preset.scale(0.0))),
+                    texture: textures.choose(&mut rng).cloned().unwrap_or_default(),
+                    sprite: Sprite {
+                        color: preset.color(0.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                Particle {
+                    effect: emitter.effect,
+                    velocity: Vec3::new(angle.cos(), angle.sin(), 0.0) * speed,
+                    timer: Timer::from_seconds(lifetime, TimerMode::Once),
+                },
+            ));
+        }
+        emitter.emitted = due;
+
+        if emitter.timer.finished() {
+            commands.entity(emitter_entity).despawn();
+        }
+    }
+}
+
+// Moves the particles and takes them through the preset's colors and scales,
+// despawning them once their life is over.
+pub fn update_particles(
+    mut commands: Commands,
+    mut particle_query: Query<(Entity, &mut Transform, &mut Sprite, &mut Particle)>,
+    particle_library: Res<ParticleLibrary>,
+    time: Res<Time>,
+) {
+    let delta_seconds = time.delta_seconds();
+
+    for (particle_entity, mut transform, mut sprite, mut particle) in particle_query.iter_mut() {
+        particle.timer.tick(time.delta());
+
+        let preset = match particle_library.presets.get(&particle.effect) {
+            Some((preset, _)) if !particle.timer.finished() => preset,
+            _ => {
+                commands.entity(particle_entity).despawn();
+                continue;
+            }
+        };
+
+        particle.velocity *= (1.0 - preset.damping * delta_seconds).max(0.0);
+        transform.translation += particle.velocity * delta_seconds;
+
+        let fraction = particle.timer.percent();
+        transform.scale = Vec3::splat(preset.scale(fraction));
+        sprite.color = preset.color(fraction);
+    }
+}
+
+pub fn despawn_particles(
+    mut commands: Commands,
+    particle_query: Query<Entity, With<Particle>>,
+    emitter_query: Query<Entity, With<ParticleEmitter>>,
+) {
+    for particle_entity in particle_query.iter() {
+        commands.entity(particle_entity).despawn();
+    }
+    for emitter_entity in emitter_query.iter() {
+        commands.entity(emitter_entity).despawn();
+    }
+}
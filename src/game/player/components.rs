@@ -1,4 +1,92 @@
+use super::PLAYER_COLORS;
+use crate::game::surface::components::Surface;
+use crate::game::surface::DEFAULT_STEP_LENGTH;
+
 use bevy::prelude::*;
 
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PlayerId(pub usize);
+
+impl PlayerId {
+    // Tint telling the players apart.
+    pub fn color(&self) -> Color {
+        PLAYER_COLORS[self.0 % PLAYER_COLORS.len()]
+    }
+}
+
+#[derive(Component)]
+pub struct Player {
+    pub id: PlayerId,
+}
+
+// What the player wants to do this frame, filled from the player's input binding.
+#[derive(Component, Default)]
+pub struct PlayerIntent {
+    pub direction: Vec3,
+    pub dash: bool,
+    // Where the player is shooting at, if shooting at all.
+    pub fire: Option<Vec3>,
+}
+
+// Marks players whose intent comes over the network instead of the local input bindings.
 #[derive(Component)]
-pub struct Player;
+pub struct RemoteInput {}
+
+#[derive(Component, Clone)]
+pub struct Lives {
+    pub value: usize,
+}
+
+// Grace period after losing a life, the player blinks and can't be hit.
+#[derive(Component, Clone)]
+pub struct Invulnerable {
+    pub timer: Timer,
+}
+
+// Present on the player while dashing, the player is invulnerable for the whole dash.
+#[derive(Component, Clone)]
+pub struct Dash {
+    pub direction: Vec3,
+    pub timer: Timer,
+    pub trail_timer: Timer,
+}
+
+#[derive(Component, Clone)]
+pub struct DashCooldown {
+    pub timer: Timer,
+}
+
+// Fading copy of the player left behind while dashing.
+#[derive(Component)]
+pub struct DashTrail {
+    pub timer: Timer,
+}
+
+#[derive(Component, Default, Clone)]
+pub struct Velocity {
+    pub value: Vec3,
+}
+
+// The surface under the player and how it affects the movement, kept up to date by the
+// surface plugin.
+#[derive(Component, Clone)]
+pub struct Ground {
+    pub surface: Surface,
+    pub speed: f32,
+    pub friction: f32,
+    pub step_length: f32,
+    // Distance walked since the last footstep.
+    pub stride: f32,
+}
+
+impl Default for Ground {
+    fn default() -> Self {
+        Ground {
+            surface: Surface::default(),
+            speed: 1.0,
+            friction: 1.0,
+            step_length: DEFAULT_STEP_LENGTH,
+            stride: 0.0,
+        }
+    }
+}
@@ -1,34 +1,83 @@
-mod components;
+pub mod components;
+pub mod resources;
 mod systems;
 
-use super::GameState;
+use super::simulation::{run_simulation, SimulationSchedule};
+use super::{in_versus_mode, GameState};
+use crate::events::{PlayerHit, PlayerOut, SpawnPlayer};
 use crate::AppState;
-
+use resources::*;
 use systems::*;
 
+pub use systems::{
+    check_star_collision, consume_dash, keyboard_intent, move_player, player_bundle,
+};
+
 use bevy::prelude::*;
 
 pub const PLAYER_SPEED: f32 = 500.0;
 pub const PLAYER_SIZE: f32 = 64.0; // Player's sprite size.
+pub const MAX_PLAYERS: usize = 4;
+pub const PLAYER_COLORS: [Color; MAX_PLAYERS] = [
+    Color::WHITE,
+    Color::rgb(1.0, 0.75, 0.3),
+    Color::rgb(0.5, 1.0, 0.5),
+    Color::rgb(1.0, 0.5, 1.0),
+];
+pub const PLAYER_LIVES: usize = 3;
+pub const INVULNERABILITY_DURATION: f32 = 2.0;
+pub const GAMEPAD_DEADZONE: f32 = 0.2;
+pub const MOVEMENT_CONFIG_PATH: &str = "assets/config/movement.ron";
+pub const DASH_SPEED: f32 = 1600.0;
+pub const DASH_DURATION: f32 = 0.15;
+pub const DASH_COOLDOWN: f32 = 1.5;
+pub const DASH_TRAIL_PERIOD: f32 = 0.02; // How often a trail sprite is left behind while dashing.
+pub const DASH_TRAIL_LIFETIME: f32 = 0.3;
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app
-            // Upon entering the game state, spawn player
+        app.init_resource::<MovementConfig>()
+            .init_resource::<PlayerCount>()
+            .init_resource::<PlayerBindings>()
+            .add_event::<PlayerHit>()
+            .add_event::<PlayerOut>()
+            .add_event::<SpawnPlayer>()
+            .add_startup_system(load_movement_config)
+            .add_system(select_player_count.run_if(in_state(AppState::Menu)))
+            // Upon entering the game state, spawn players
             .add_system(spawn_player.in_schedule(OnEnter(AppState::Game)))
-            // Upon exitting the game state, despawn player
+            .add_system(spawn_requested_players.in_set(OnUpdate(AppState::Game)))
+            // Upon exitting the game state, despawn players and the dash leftovers
             .add_system(despawn_player.in_schedule(OnExit(AppState::Game)))
+            .add_system(despawn_dash_trail.in_schedule(OnExit(AppState::Game)))
             .add_systems(
                 (
-                    move_player,
-                    limit_player_movement.after(move_player),
-                    check_enemy_collision,
-                    check_star_collision,
+                    // Input is read once per frame, the simulation may run several ticks with it.
+                    read_player_input.before(run_simulation),
+                    fade_dash_trail,
                 )
                     .in_set(OnUpdate(AppState::Game))
                     .in_set(OnUpdate(GameState::Running)),
+            )
+            .add_systems(
+                (
+                    start_dash,
+                    consume_dash.after(start_dash),
+                    move_player.after(start_dash),
+                    dash_player.after(start_dash),
+                    limit_player_movement.after(move_player).after(dash_player),
+                    dash_cooldown_tick,
+                    check_enemy_collision,
+                    // Versus mode handles the hits on its own.
+                    handle_player_hit
+                        .after(check_enemy_collision)
+                        .run_if(not(in_versus_mode)),
+                    update_invulnerability,
+                    check_star_collision,
+                )
+                    .in_schedule(SimulationSchedule),
             );
     }
 }
@@ -0,0 +1,81 @@
+use super::{MAX_PLAYERS, PLAYER_SPEED};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Deserialize)]
+pub enum MovementModel {
+    // Player moves at max speed while a key is held and stops right away.
+    #[default]
+    Arcade,
+    // Player accelerates towards max speed and slows down due to friction.
+    Inertial,
+}
+
+// Movement parameters, loaded from MOVEMENT_CONFIG_PATH at startup.
+#[derive(Resource, Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MovementConfig {
+    pub model: MovementModel,
+    pub max_speed: f32,
+    pub acceleration: f32,
+    // Fraction of the velocity lost per second.
+    pub friction: f32,
+    pub wall_bounce: bool,
+    // Fraction of the velocity kept after bouncing off a wall.
+    pub bounce_restitution: f32,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        MovementConfig {
+            model: MovementModel::Arcade,
+            max_speed: PLAYER_SPEED,
+            acceleration: 2500.0,
+            friction: 4.0,
+            wall_bounce: true,
+            bounce_restitution: 0.6,
+        }
+    }
+}
+
+// Number of players in the next run. Chosen in the menu before entering the game.
+#[derive(Resource)]
+pub struct PlayerCount {
+    pub value: usize,
+}
+
+impl Default for PlayerCount {
+    fn default() -> Self {
+        PlayerCount { value: 1 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum InputBinding {
+    // WASD to move, left shift to dash.
+    Wasd,
+    // Arrow keys to move, right shift to dash.
+    Arrows,
+    // Left stick or d-pad to move, south button to dash. Index among the connected gamepads.
+    Gamepad(usize),
+}
+
+// Input binding of every player, indexed by the player id.
+#[derive(Resource)]
+pub struct PlayerBindings {
+    pub bindings: [InputBinding; MAX_PLAYERS],
+}
+
+impl Default for PlayerBindings {
+    fn default() -> Self {
+        PlayerBindings {
+            bindings: [
+                InputBinding::Wasd,
+                InputBinding::Arrows,
+                InputBinding::Gamepad(0),
+                InputBinding::Gamepad(1),
+            ],
+        }
+    }
+}
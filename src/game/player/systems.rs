@@ -1,42 +1,99 @@
-use super::components::Player;
-use super::{PLAYER_SIZE, PLAYER_SPEED};
-use crate::events::GameOver;
+use super::components::*;
+use super::resources::*;
+use super::{
+    DASH_COOLDOWN, DASH_DURATION, DASH_SPEED, DASH_TRAIL_LIFETIME, DASH_TRAIL_PERIOD,
+    GAMEPAD_DEADZONE, INVULNERABILITY_DURATION, MAX_PLAYERS, MOVEMENT_CONFIG_PATH, PLAYER_LIVES,
+    PLAYER_SIZE,
+};
+use crate::events::{
+    CameraEffect, GameOver, HitSource, ParticleBurst, ParticleEffect, PlayerHit, PlayerOut, Sound,
+    SoundEvent, SpawnPlayer, StarCollected,
+};
+use crate::game::camera::{HIT_TRAUMA, OUT_HIT_STOP, OUT_TRAUMA};
 use crate::game::enemy::components::*;
 use crate::game::enemy::ENEMY_SIZE;
 use crate::game::score::resources::*;
+use crate::game::simulation::resources::GameRng;
 use crate::game::star::components::*;
-use crate::game::star::STAR_SIZE;
+use crate::game::star::{STAR_SCORE, STAR_SIZE};
+use crate::game::{GameMode, ARENA_HEIGHT, ARENA_WIDTH};
 
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
+use std::time::Duration;
 
-// Update player transform every frame.
-pub fn move_player(
-    time: Res<Time>,
-    mut player_query: Query<&mut Transform, With<Player>>,
+// Fills every player's intent from their input binding.
+// Players controlled over the network get their intent from the server instead.
+#[allow(clippy::too_many_arguments)]
+pub fn read_player_input(
+    mut player_query: Query<(&Player, &mut PlayerIntent), Without<RemoteInput>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
     keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    player_bindings: Res<PlayerBindings>,
 ) {
-    // Do not move non-existent player.
-    let mut player_transform = match player_query.get_single_mut() {
-        Ok(v) => v,
-        Err(_error) => {
-            // println!("Error when moving the player: {}", error);
-            return;
+    // The camera scales and shakes the arena, so the cursor goes through it to land in the world.
+    let cursor_world_position = match (window_query.get_single(), camera_query.get_single()) {
+        (Ok(window), Ok((camera, camera_transform))) => {
+            window.cursor_position().and_then(|cursor_position| {
+                camera.viewport_to_world_2d(camera_transform, cursor_position)
+            })
         }
+        _ => None,
     };
 
+    for (player, mut intent) in player_query.iter_mut() {
+        let binding = player_bindings.bindings[player.id.0 % MAX_PLAYERS];
+        // Frames can go by without a tick, the dash waits for one to use it up.
+        let dash_pending = intent.dash;
+
+        *intent = match binding {
+            InputBinding::Wasd => keyboard_intent(
+                &keyboard_input,
+                [KeyCode::W, KeyCode::S, KeyCode::A, KeyCode::D],
+                KeyCode::LShift,
+            ),
+            InputBinding::Arrows => keyboard_intent(
+                &keyboard_input,
+                [KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right],
+                KeyCode::RShift,
+            ),
+            InputBinding::Gamepad(index) => match gamepads.iter().nth(index) {
+                Some(gamepad) => gamepad_intent(gamepad, &gamepad_buttons, &gamepad_axes),
+                None => PlayerIntent::default(),
+            },
+        };
+        intent.dash |= dash_pending;
+
+        // The mouse belongs to the first player, who shares the keyboard with it.
+        if player.id == PlayerId(0) && mouse_input.pressed(MouseButton::Left) {
+            intent.fire = cursor_world_position.map(|position| position.extend(0.0));
+        }
+    }
+}
+
+// Builds the intent from the up, down, left and right keys plus the dash key.
+pub fn keyboard_intent(
+    keyboard_input: &Input<KeyCode>,
+    [up, down, left, right]: [KeyCode; 4],
+    dash: KeyCode,
+) -> PlayerIntent {
     let mut direction = Vec3::ZERO;
 
-    if keyboard_input.pressed(KeyCode::W) {
+    if keyboard_input.pressed(up) {
         direction.y += 1.0;
     }
-    if keyboard_input.pressed(KeyCode::S) {
+    if keyboard_input.pressed(down) {
         direction.y -= 1.0;
     }
-    if keyboard_input.pressed(KeyCode::A) {
+    if keyboard_input.pressed(left) {
         direction.x -= 1.0;
     }
-    if keyboard_input.pressed(KeyCode::D) {
+    if keyboard_input.pressed(right) {
         direction.x += 1.0;
     }
 
@@ -44,108 +101,425 @@ pub fn move_player(
         direction = direction.normalize();
     }
 
-    player_transform.translation += direction * PLAYER_SPEED * time.delta_seconds();
+    PlayerIntent {
+        direction,
+        dash: keyboard_input.just_pressed(dash),
+        ..default()
+    }
 }
 
-// Prevents player for going off the screen borders.
-pub fn limit_player_movement(
-    mut player_query: Query<&mut Transform, With<Player>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+fn gamepad_intent(
+    gamepad: Gamepad,
+    gamepad_buttons: &Input<GamepadButton>,
+    gamepad_axes: &Axis<GamepadAxis>,
+) -> PlayerIntent {
+    let axis = |axis_type| {
+        gamepad_axes
+            .get(GamepadAxis::new(gamepad, axis_type))
+            .unwrap_or(0.0)
+    };
+    let button = |button_type| gamepad_buttons.pressed(GamepadButton::new(gamepad, button_type));
+
+    let mut direction = Vec3::new(
+        axis(GamepadAxisType::LeftStickX),
+        axis(GamepadAxisType::LeftStickY),
+        0.0,
+    );
+    if direction.length() < GAMEPAD_DEADZONE {
+        direction = Vec3::ZERO;
+    }
+
+    if button(GamepadButtonType::DPadUp) {
+        direction.y += 1.0;
+    }
+    if button(GamepadButtonType::DPadDown) {
+        direction.y -= 1.0;
+    }
+    if button(GamepadButtonType::DPadLeft) {
+        direction.x -= 1.0;
+    }
+    if button(GamepadButtonType::DPadRight) {
+        direction.x += 1.0;
+    }
+
+    PlayerIntent {
+        direction: direction.clamp_length_max(1.0),
+        dash: gamepad_buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::South)),
+        ..default()
+    }
+}
+
+// Update players velocity and transform every frame according to the movement model.
+#[allow(clippy::type_complexity)]
+pub fn move_player(
+    time: Res<Time>,
+    mut player_query: Query<
+        (&mut Transform, &mut Velocity, &mut Ground, &PlayerIntent),
+        (With<Player>, Without<Dash>),
+    >,
+    movement_config: Res<MovementConfig>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
 ) {
-    // Do not move non-existent player.
-    let mut player_transform = match player_query.get_single_mut() {
+    let delta_seconds = time.delta_seconds();
+
+    // Dashing players are moved by dash_player.
+    for (mut player_transform, mut velocity, mut ground, intent) in player_query.iter_mut() {
+        let max_speed = movement_config.max_speed * ground.speed;
+        let friction = movement_config.friction * ground.friction;
+
+        match movement_config.model {
+            MovementModel::Arcade => {
+                velocity.value = intent.direction * max_speed;
+            }
+            MovementModel::Inertial => {
+                velocity.value += intent.direction * movement_config.acceleration * delta_seconds;
+                velocity.value *= (1.0 - friction * delta_seconds).max(0.0);
+                velocity.value = velocity.value.clamp_length_max(max_speed);
+            }
+        }
+
+        player_transform.translation += velocity.value * delta_seconds;
+
+        // A step every step length walked, the faster the player the quicker the steps.
+        ground.stride += velocity.value.length() * delta_seconds;
+        if ground.stride >= ground.step_length {
+            ground.stride = 0.0;
+            sound_event_writer.send(SoundEvent::at(
+                Sound::Footstep(ground.surface),
+                player_transform.translation,
+            ));
+        }
+    }
+}
+
+// Reads the movement config, keeping the defaults if it's missing or malformed.
+pub fn load_movement_config(mut movement_config: ResMut<MovementConfig>) {
+    let config = match std::fs::read_to_string(MOVEMENT_CONFIG_PATH) {
         Ok(v) => v,
-        Err(_error) => {
-            // println!("Error when limiting player movement: {}", error);
+        Err(error) => {
+            println!("Could not read {}: {}", MOVEMENT_CONFIG_PATH, error);
             return;
         }
     };
-    let primary_window = window_query.get_single().unwrap();
 
-    let player_half_size = PLAYER_SIZE / 2.0;
-    let x_min = 0.0 + player_half_size;
-    let x_max = primary_window.width() - player_half_size;
-    let y_min = 0.0 + player_half_size;
-    let y_max = primary_window.height() - player_half_size;
+    match ron::from_str::<MovementConfig>(&config) {
+        Ok(v) => *movement_config = v,
+        Err(error) => println!("Could not parse {}: {}", MOVEMENT_CONFIG_PATH, error),
+    }
+}
+
+// Starts a dash in the movement direction if the player's dash is off cooldown.
+pub fn start_dash(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &Transform, &PlayerIntent, &mut DashCooldown), Without<Dash>>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+) {
+    for (player_entity, player_transform, intent, mut dash_cooldown) in player_query.iter_mut() {
+        // Can't dash while standing still.
+        if !intent.dash || !dash_cooldown.timer.finished() || intent.direction == Vec3::ZERO {
+            continue;
+        }
+
+        commands.entity(player_entity).insert(Dash {
+            direction: intent.direction.normalize(),
+            timer: Timer::from_seconds(DASH_DURATION, TimerMode::Once),
+            trail_timer: Timer::from_seconds(DASH_TRAIL_PERIOD, TimerMode::Repeating),
+        });
+
+        dash_cooldown.timer.reset();
+
+        sound_event_writer.send(SoundEvent::at(Sound::Dash, player_transform.translation));
+    }
+}
+
+// The dash is a one-off action, it's used up by the tick that got to see it.
+pub fn consume_dash(mut intent_query: Query<&mut PlayerIntent>) {
+    for mut intent in intent_query.iter_mut() {
+        if intent.dash {
+            intent.dash = false;
+        }
+    }
+}
+
+// Moves the dashing players and leaves a trail behind, stops the dash once it's over.
+pub fn dash_player(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &Player, &mut Transform, &mut Velocity, &mut Dash)>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    movement_config: Res<MovementConfig>,
+) {
+    for (player_entity, player, mut player_transform, mut velocity, mut dash) in
+        player_query.iter_mut()
+    {
+        player_transform.translation += dash.direction * DASH_SPEED * time.delta_seconds();
+
+        dash.trail_timer.tick(time.delta());
+        if dash.trail_timer.just_finished() {
+            commands.spawn((
+                SpriteBundle {
+                    // Keep the trail below the player.
+                    transform: Transform::from_translation(
+                        player_transform.translation - Vec3::Z * 0.1,
+                    ),
+                    texture: asset_server.load("sprites/Default/ball_blue_large.png"),
+                    sprite: Sprite {
+                        color: player.id.color().with_a(0.5),
+                        ..default()
+                    },
+                    ..default()
+                },
+                DashTrail {
+                    timer: Timer::from_seconds(DASH_TRAIL_LIFETIME, TimerMode::Once),
+                },
+            ));
+        }
 
-    let mut player_translation = player_transform.translation;
-    if player_translation.x < x_min {
-        player_translation.x = x_min;
+        dash.timer.tick(time.delta());
+        if dash.timer.finished() {
+            // Keep the momentum going in the dash direction.
+            velocity.value = dash.direction * movement_config.max_speed;
+            commands.entity(player_entity).remove::<Dash>();
+        }
     }
-    if player_translation.x > x_max {
-        player_translation.x = x_max;
+}
+
+pub fn fade_dash_trail(
+    mut commands: Commands,
+    mut trail_query: Query<(Entity, &mut Sprite, &mut DashTrail)>,
+    time: Res<Time>,
+) {
+    for (trail_entity, mut sprite, mut trail) in trail_query.iter_mut() {
+        trail.timer.tick(time.delta());
+
+        if trail.timer.finished() {
+            commands.entity(trail_entity).despawn();
+        } else {
+            sprite.color.set_a(0.5 * trail.timer.percent_left());
+        }
     }
-    if player_translation.y < y_min {
-        player_translation.y = y_min;
+}
+
+pub fn despawn_dash_trail(mut commands: Commands, trail_query: Query<Entity, With<DashTrail>>) {
+    for trail_entity in trail_query.iter() {
+        commands.entity(trail_entity).despawn();
     }
-    if player_translation.y > y_max {
-        player_translation.y = y_max;
+}
+
+pub fn dash_cooldown_tick(mut dash_cooldown_query: Query<&mut DashCooldown>, time: Res<Time>) {
+    for mut dash_cooldown in dash_cooldown_query.iter_mut() {
+        dash_cooldown.timer.tick(time.delta());
     }
+}
+
+// Prevents players for going off the screen borders. Inertial players may bounce off them.
+pub fn limit_player_movement(
+    mut player_query: Query<(&mut Transform, &mut Velocity), With<Player>>,
+    movement_config: Res<MovementConfig>,
+) {
+    let player_half_size = PLAYER_SIZE / 2.0;
+    let x_min = 0.0 + player_half_size;
+    let x_max = ARENA_WIDTH - player_half_size;
+    let y_min = 0.0 + player_half_size;
+    let y_max = ARENA_HEIGHT - player_half_size;
 
-    player_transform.translation = player_translation;
+    let bounce = movement_config.model == MovementModel::Inertial && movement_config.wall_bounce;
+    let wall_factor = if bounce {
+        -movement_config.bounce_restitution
+    } else {
+        0.0
+    };
+
+    for (mut player_transform, mut velocity) in player_query.iter_mut() {
+        let mut player_translation = player_transform.translation;
+        if player_translation.x < x_min {
+            player_translation.x = x_min;
+            velocity.value.x *= wall_factor;
+        }
+        if player_translation.x > x_max {
+            player_translation.x = x_max;
+            velocity.value.x *= wall_factor;
+        }
+        if player_translation.y < y_min {
+            player_translation.y = y_min;
+            velocity.value.y *= wall_factor;
+        }
+        if player_translation.y > y_max {
+            player_translation.y = y_max;
+            velocity.value.y *= wall_factor;
+        }
+
+        player_transform.translation = player_translation;
+    }
 }
 
-// Checks if the player and enemy collide with each other.
+// Checks if the players and enemies collide with each other.
+// Dashing and recently hit players are invulnerable.
+#[allow(clippy::type_complexity)]
 pub fn check_enemy_collision(
+    player_query: Query<(Entity, &Transform), (With<Player>, Without<Dash>, Without<Invulnerable>)>,
+    mut player_hit_event_writer: EventWriter<PlayerHit>,
+    enemy_query: Query<&Transform, With<Enemy>>,
+) {
+    let player_radius = PLAYER_SIZE / 2.0;
+    let enemy_radius = ENEMY_SIZE / 2.0;
+
+    for (player_entity, player_transform) in player_query.iter() {
+        let player_translation = player_transform.translation;
+
+        for enemy_transform in enemy_query.iter() {
+            let enemy_translation = enemy_transform.translation;
+
+            let distance = f32::sqrt(
+                (player_translation.x - enemy_translation.x)
+                    * (player_translation.x - enemy_translation.x)
+                    + (player_translation.y - enemy_translation.y)
+                        * (player_translation.y - enemy_translation.y),
+            );
+            if distance < player_radius + enemy_radius {
+                player_hit_event_writer.send(PlayerHit {
+                    player_entity,
+                    source: HitSource::Enemy,
+                });
+                break;
+            }
+        }
+    }
+}
+
+// Takes a life from every hit player. Players without lives are out,
+// the game is over once every player is out.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_player_hit(
     mut commands: Commands,
-    mut player_query: Query<(Entity, &Transform), With<Player>>,
+    mut player_hit_event_reader: EventReader<PlayerHit>,
+    mut player_query: Query<(&Player, &Transform, &mut Lives), Without<Invulnerable>>,
+    all_players_query: Query<&Player>,
     mut game_over_event_writer: EventWriter<GameOver>,
-    enemy_query: Query<&Transform, With<Enemy>>,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
+    mut player_out_event_writer: EventWriter<PlayerOut>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    mut particle_burst_event_writer: EventWriter<ParticleBurst>,
+    mut camera_effect_event_writer: EventWriter<CameraEffect>,
     score: Res<Score>,
+    player_count: Res<PlayerCount>,
+    game_rng: Res<GameRng>,
 ) {
-    let (player_entity, player_transform) = match player_query.get_single_mut() {
-        Ok(v) => v,
-        Err(_error) => {
-            // println!("Error when checking collision of player: {}", error);
-            return;
+    let mut hit_players: Vec<Entity> = Vec::new();
+    let mut players_out = 0;
+
+    for event in player_hit_event_reader.iter() {
+        // Player might get hit by several things at once.
+        if hit_players.contains(&event.player_entity) {
+            continue;
         }
-    };
+        let (player, player_transform, mut lives) = match player_query.get_mut(event.player_entity)
+        {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        hit_players.push(event.player_entity);
 
-    let player_radius = PLAYER_SIZE / 2.0;
-    let player_translation = player_transform.translation;
-
-    for enemy_transform in enemy_query.iter() {
-        let enemy_radius = ENEMY_SIZE / 2.0;
-        let enemy_translation = enemy_transform.translation;
-
-        let distance = f32::sqrt(
-            (player_translation.x - enemy_translation.x)
-                * (player_translation.x - enemy_translation.x)
-                + (player_translation.y - enemy_translation.y)
-                    * (player_translation.y - enemy_translation.y),
-        );
-        if distance < player_radius + enemy_radius {
-            // Send GameOver event.
-            game_over_event_writer.send(GameOver {
-                score_value: score.value,
+        lives.value = lives.value.saturating_sub(1);
+
+        if lives.value > 0 {
+            println!(
+                "Player {} lost a life, {} left",
+                player.id.0 + 1,
+                lives.value
+            );
+
+            sound_event_writer.send(SoundEvent::at(
+                Sound::PlayerHit,
+                player_transform.translation,
+            ));
+            camera_effect_event_writer.send(CameraEffect::Shake(HIT_TRAUMA));
+
+            commands.entity(event.player_entity).insert(Invulnerable {
+                timer: Timer::from_seconds(INVULNERABILITY_DURATION, TimerMode::Once),
             });
+            continue;
+        }
+
+        println!("Player {} is out!", player.id.0 + 1);
+        player_out_event_writer.send(PlayerOut {
+            source: event.source,
+        });
+
+        // Play the sound.
+        sound_event_writer.send(SoundEvent::at(
+            Sound::PlayerOut,
+            player_transform.translation,
+        ));
+        particle_burst_event_writer.send(ParticleBurst::at(
+            ParticleEffect::PlayerOut,
+            player_transform.translation,
+        ));
+        camera_effect_event_writer.send(CameraEffect::Shake(OUT_TRAUMA));
+        camera_effect_event_writer.send(CameraEffect::HitStop(OUT_HIT_STOP));
+
+        // Despawn player.
+        commands.entity(event.player_entity).despawn();
+        players_out += 1;
+    }
 
-            // Play the sound.
-            let sound_effect = asset_server.load("audio/scifi/explosionCrunch_000.ogg");
-            audio.play(sound_effect);
+    // Players that are out are already despawned, so only the ones out this frame are left.
+    if players_out > 0 && all_players_query.iter().count() == players_out {
+        // Send GameOver event.
+        game_over_event_writer.send(GameOver {
+            score_value: score.value,
+            players: score.player_results(player_count.value),
+            seed: game_rng.seed,
+        });
+    }
+}
 
-            // Despawn player.
-            commands.entity(player_entity).despawn();
+// Blinks the invulnerable players and makes them vulnerable again once the time is up.
+pub fn update_invulnerability(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &Player, &mut Sprite, &mut Invulnerable)>,
+    time: Res<Time>,
+) {
+    for (player_entity, player, mut sprite, mut invulnerable) in player_query.iter_mut() {
+        invulnerable.timer.tick(time.delta());
+
+        let mut color = player.id.color();
+        if invulnerable.timer.finished() {
+            commands.entity(player_entity).remove::<Invulnerable>();
+        } else if ((invulnerable.timer.elapsed_secs() * 10.0) as usize).is_multiple_of(2) {
+            color.set_a(0.3);
         }
+        sprite.color = color;
     }
 }
 
-// Checks if the player and star collide with each other.
+// Checks if the players and stars collide with each other.
+#[allow(clippy::too_many_arguments)]
 pub fn check_star_collision(
     mut commands: Commands,
-    player_query: Query<&Transform, With<Player>>,
+    player_query: Query<(&Transform, &Player)>,
     star_query: Query<(&Transform, Entity), With<Star>>,
-    audio: Res<Audio>,
-    asset_server: Res<AssetServer>,
+    mut star_collected_event_writer: EventWriter<StarCollected>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    mut particle_burst_event_writer: EventWriter<ParticleBurst>,
     mut score: ResMut<Score>,
+    combo: Res<Combo>,
+    game_mode: Res<GameMode>,
 ) {
-    if let Ok(player_transform) = player_query.get_single() {
-        let player_radius = PLAYER_SIZE / 2.0;
+    let player_radius = PLAYER_SIZE / 2.0;
+    let star_radius = STAR_SIZE / 2.0;
+    // The combo is shared by every player, so it would pay the rival in versus.
+    let multiplier = if *game_mode == GameMode::Versus {
+        1
+    } else {
+        combo.multiplier()
+    };
+    let mut collected_stars: Vec<Entity> = Vec::new();
 
+    for (player_transform, player) in player_query.iter() {
         for (star_transform, star_entity) in star_query.iter() {
-            let star_radius = STAR_SIZE / 2.0;
+            if collected_stars.contains(&star_entity) {
+                continue;
+            }
 
             if player_transform
                 .translation
@@ -153,39 +527,121 @@ pub fn check_star_collision(
                 < player_radius + star_radius
             {
                 // Play the star sound, increment the score, and despawn the star.
-                let star_sound = asset_server.load("audio/interface/confirmation_001.ogg");
-                audio.play(star_sound);
+                sound_event_writer.send(SoundEvent::at(
+                    Sound::StarCollected,
+                    star_transform.translation,
+                ));
+                particle_burst_event_writer.send(ParticleBurst::at(
+                    ParticleEffect::StarCollected,
+                    star_transform.translation,
+                ));
 
-                score.value += 1;
+                score.award(player.id, STAR_SCORE * multiplier);
+                star_collected_event_writer.send(StarCollected {
+                    position: star_transform.translation,
+                    points: STAR_SCORE * multiplier,
+                    multiplier,
+                });
 
                 commands.entity(star_entity).despawn();
+                collected_stars.push(star_entity);
             }
         }
     }
 }
 
+// Spawns the players next to each other in the middle of the arena.
 pub fn spawn_player(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
     asset_server: Res<AssetServer>,
+    player_count: Res<PlayerCount>,
+) {
+    for index in 0..player_count.value {
+        let offset = (index as f32 - (player_count.value - 1) as f32 / 2.0) * PLAYER_SIZE * 2.0;
+        let translation = Vec3::new(ARENA_WIDTH / 2.0 + offset, ARENA_HEIGHT / 2.0, 0.0);
+
+        commands.spawn(player_bundle(PlayerId(index), translation, &asset_server));
+    }
+}
+
+// Spawns the players that join an already running game.
+pub fn spawn_requested_players(
+    mut commands: Commands,
+    mut spawn_player_event_reader: EventReader<SpawnPlayer>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in spawn_player_event_reader.iter() {
+        // Every id has its own spot, so players joining one by one don't overlap.
+        let offset = (event.id.0 as f32 - (MAX_PLAYERS - 1) as f32 / 2.0) * PLAYER_SIZE * 2.0;
+        let translation = Vec3::new(ARENA_WIDTH / 2.0 + offset, ARENA_HEIGHT / 2.0, 0.0);
+
+        let mut player_entity = commands.spawn(player_bundle(event.id, translation, &asset_server));
+        if event.remote {
+            player_entity.insert(RemoteInput {});
+        }
+    }
+}
+
+pub fn player_bundle(
+    id: PlayerId,
+    translation: Vec3,
+    asset_server: &AssetServer,
+) -> (
+    SpriteBundle,
+    Player,
+    PlayerIntent,
+    Lives,
+    DashCooldown,
+    Velocity,
+    Ground,
 ) {
-    let window = window_query.get_single().unwrap();
+    // Dash is ready right from the start.
+    let mut dash_timer = Timer::from_seconds(DASH_COOLDOWN, TimerMode::Once);
+    dash_timer.tick(Duration::from_secs_f32(DASH_COOLDOWN));
 
-    commands.spawn((
+    (
         SpriteBundle {
-            transform: Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, 0.0),
+            transform: Transform::from_translation(translation),
             texture: asset_server.load("sprites/Default/ball_blue_large.png"),
+            sprite: Sprite {
+                color: id.color(),
+                ..default()
+            },
             ..default()
         },
-        Player {},
-    ));
+        Player { id },
+        PlayerIntent::default(),
+        Lives {
+            value: PLAYER_LIVES,
+        },
+        DashCooldown { timer: dash_timer },
+        Velocity::default(),
+        Ground::default(),
+    )
 }
 
 pub fn despawn_player(mut commands: Commands, player_query: Query<Entity, With<Player>>) {
-    let player_entity = match player_query.get_single() {
-        Ok(player_entity) => player_entity,
-        Err(_) => return,
-    };
+    for player_entity in player_query.iter() {
+        commands.entity(player_entity).despawn();
+    }
+}
 
-    commands.entity(player_entity).despawn();
+// Lets the players choose how many of them are going to play while in the menu.
+pub fn select_player_count(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut player_count: ResMut<PlayerCount>,
+    game_mode: Res<GameMode>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::P) {
+        return;
+    }
+
+    // Cycles through the counts the selected mode allows.
+    let player_range = game_mode.player_range();
+    player_count.value = if player_count.value >= *player_range.end() {
+        *player_range.start()
+    } else {
+        (player_count.value + 1).max(*player_range.start())
+    };
+    println!("Players: {}", player_count.value);
 }
@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct Projectile {
+    pub direction: Vec3,
+}
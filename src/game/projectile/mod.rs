@@ -0,0 +1,42 @@
+pub mod components;
+pub mod resources;
+mod systems;
+
+use super::shooting_enabled;
+use super::simulation::SimulationSchedule;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const PROJECTILE_SPEED: f32 = 900.0;
+pub const PROJECTILE_SIZE: f32 = 16.0;
+pub const FIRE_PERIOD: f32 = 0.2; // Minimal time between two shots.
+pub const START_AMMO: usize = 10;
+pub const MAX_AMMO: usize = 20;
+pub const AMMO_PER_STAR: usize = 2;
+pub const ENEMY_KILL_SCORE: usize = 3;
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Ammo>()
+            .init_resource::<FireTimer>()
+            // Upon leaving the game state, despawn projectiles and refill the ammo
+            .add_system(despawn_projectiles.in_schedule(OnExit(AppState::Game)))
+            .add_system(reset_ammo.in_schedule(OnExit(AppState::Game)))
+            .add_systems(
+                (
+                    fire_projectile,
+                    move_projectiles,
+                    check_projectile_collision.after(move_projectiles),
+                    refill_ammo,
+                    fire_timer_tick,
+                )
+                    .distributive_run_if(shooting_enabled)
+                    .in_schedule(SimulationSchedule),
+            );
+    }
+}
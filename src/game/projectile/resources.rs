@@ -0,0 +1,27 @@
+use super::{FIRE_PERIOD, START_AMMO};
+
+use bevy::prelude::*;
+
+#[derive(Resource)]
+pub struct Ammo {
+    pub value: usize,
+}
+
+impl Default for Ammo {
+    fn default() -> Self {
+        Ammo { value: START_AMMO }
+    }
+}
+
+#[derive(Resource)]
+pub struct FireTimer {
+    pub timer: Timer,
+}
+
+impl Default for FireTimer {
+    fn default() -> Self {
+        FireTimer {
+            timer: Timer::from_seconds(FIRE_PERIOD, TimerMode::Once),
+        }
+    }
+}
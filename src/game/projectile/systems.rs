@@ -0,0 +1,151 @@
+use super::components::*;
+use super::resources::*;
+use super::{AMMO_PER_STAR, ENEMY_KILL_SCORE, MAX_AMMO, PROJECTILE_SIZE, PROJECTILE_SPEED};
+use crate::events::{ParticleBurst, ParticleEffect, Sound, SoundEvent, StarCollected};
+use crate::game::enemy::components::Enemy;
+use crate::game::enemy::ENEMY_SIZE;
+use crate::game::player::components::{Player, PlayerId, PlayerIntent};
+use crate::game::score::resources::Score;
+use crate::game::{ARENA_HEIGHT, ARENA_WIDTH};
+
+use bevy::prelude::*;
+
+// Shoots towards the target of the first player that wants to shoot.
+pub fn fire_projectile(
+    mut commands: Commands,
+    player_query: Query<(&Transform, &PlayerIntent), With<Player>>,
+    asset_server: Res<AssetServer>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    mut fire_timer: ResMut<FireTimer>,
+    mut ammo: ResMut<Ammo>,
+) {
+    if !fire_timer.timer.finished() {
+        return;
+    }
+
+    let (player_transform, target) = match player_query
+        .iter()
+        .find_map(|(transform, intent)| intent.fire.map(|target| (transform, target)))
+    {
+        Some(v) => v,
+        None => return,
+    };
+
+    if ammo.value == 0 {
+        // The fire rate keeps the empty clicks from piling up.
+        sound_event_writer.send(Sound::OutOfAmmo.into());
+        fire_timer.timer.reset();
+        return;
+    }
+
+    let direction = (target - player_transform.translation).normalize_or_zero();
+    if direction == Vec3::ZERO {
+        return;
+    }
+
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_translation(player_transform.translation)
+                .with_scale(Vec3::splat(0.5)),
+            texture: asset_server.load("sprites/Default/ball_blue_small.png"),
+            ..default()
+        },
+        Projectile { direction },
+    ));
+
+    ammo.value -= 1;
+    fire_timer.timer.reset();
+
+    sound_event_writer.send(SoundEvent::at(Sound::Shot, player_transform.translation));
+}
+
+// Moves projectiles and despawns those that left the screen.
+pub fn move_projectiles(
+    mut commands: Commands,
+    mut projectile_query: Query<(Entity, &mut Transform, &Projectile)>,
+    time: Res<Time>,
+) {
+    for (projectile_entity, mut transform, projectile) in projectile_query.iter_mut() {
+        transform.translation += projectile.direction * PROJECTILE_SPEED * time.delta_seconds();
+
+        let translation = transform.translation;
+        if translation.x < 0.0
+            || translation.x > ARENA_WIDTH
+            || translation.y < 0.0
+            || translation.y > ARENA_HEIGHT
+        {
+            commands.entity(projectile_entity).despawn();
+        }
+    }
+}
+
+// Destroys the enemy hit by a projectile, rewards the player and bursts the enemy into particles.
+pub fn check_projectile_collision(
+    mut commands: Commands,
+    projectile_query: Query<(Entity, &Transform), With<Projectile>>,
+    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    mut particle_burst_event_writer: EventWriter<ParticleBurst>,
+    mut score: ResMut<Score>,
+) {
+    let mut destroyed_enemies: Vec<Entity> = Vec::new();
+
+    for (projectile_entity, projectile_transform) in projectile_query.iter() {
+        let hit_enemy = enemy_query.iter().find(|(enemy_entity, enemy_transform)| {
+            !destroyed_enemies.contains(enemy_entity)
+                && projectile_transform
+                    .translation
+                    .distance(enemy_transform.translation)
+                    < PROJECTILE_SIZE / 2.0 + ENEMY_SIZE / 2.0
+        });
+
+        let (enemy_entity, enemy_transform) = match hit_enemy {
+            Some(v) => v,
+            None => continue,
+        };
+
+        destroyed_enemies.push(enemy_entity);
+        commands.entity(projectile_entity).despawn();
+        commands.entity(enemy_entity).despawn();
+
+        score.award(PlayerId(0), ENEMY_KILL_SCORE);
+
+        sound_event_writer.send(SoundEvent::at(
+            Sound::EnemyDestroyed,
+            enemy_transform.translation,
+        ));
+
+        // Bursts the enemy into pieces.
+        particle_burst_event_writer.send(ParticleBurst::at(
+            ParticleEffect::EnemyDestroyed,
+            enemy_transform.translation,
+        ));
+    }
+}
+
+// Every collected star brings some ammo back.
+pub fn refill_ammo(
+    mut star_collected_event_reader: EventReader<StarCollected>,
+    mut ammo: ResMut<Ammo>,
+) {
+    for _ in star_collected_event_reader.iter() {
+        ammo.value = (ammo.value + AMMO_PER_STAR).min(MAX_AMMO);
+    }
+}
+
+pub fn fire_timer_tick(mut fire_timer: ResMut<FireTimer>, time: Res<Time>) {
+    fire_timer.timer.tick(time.delta());
+}
+
+pub fn despawn_projectiles(
+    mut commands: Commands,
+    projectile_query: Query<Entity, With<Projectile>>,
+) {
+    for projectile_entity in projectile_query.iter() {
+        commands.entity(projectile_entity).despawn();
+    }
+}
+
+pub fn reset_ammo(mut ammo: ResMut<Ammo>) {
+    *ammo = Ammo::default();
+}
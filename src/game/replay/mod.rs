@@ -0,0 +1,80 @@
+pub mod resources;
+mod systems;
+
+use super::player::consume_dash;
+use super::simulation::{reseed_rng, SimulationSchedule};
+use super::GameState;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const REPLAYS_DIR: &str = "replays";
+pub const MIN_PLAYBACK_SPEED: f32 = 0.25;
+pub const MAX_PLAYBACK_SPEED: f32 = 8.0;
+pub const SCRUB_SECONDS: u64 = 5; // How far the arrow keys jump through a replay.
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayRecorder>()
+            .init_resource::<ReplaySelection>()
+            .add_system(start_recording.in_schedule(OnEnter(AppState::Game)))
+            // The tick's inputs are recorded before the dash gets used up
+            .add_system(
+                record_tick
+                    .before(consume_dash)
+                    .in_schedule(SimulationSchedule),
+            )
+            .add_system(save_replay)
+            .add_systems(
+                (select_replay, play_selected_replay.after(select_replay))
+                    .distributive_run_if(in_state(AppState::Menu)),
+            )
+            // Scrubbing back restarts the run, it has to start from the replay's seed again
+            .add_system(
+                rewind_replay
+                    .after(reseed_rng)
+                    .run_if(resource_exists::<ReplayPlayback>())
+                    .in_schedule(OnExit(AppState::Game)),
+            )
+            // The playback lasts through the game over screen, so the replayed run doesn't count as a new one
+            .add_system(
+                stop_playback
+                    .run_if(resource_exists::<ReplayPlayback>())
+                    .in_schedule(OnEnter(AppState::Menu)),
+            )
+            .add_system(
+                stop_playback
+                    .run_if(resource_exists::<ReplayPlayback>())
+                    .in_schedule(OnExit(AppState::GameOver)),
+            )
+            .add_system(
+                seek_playback
+                    .before(control_playback)
+                    .run_if(resource_exists::<ReplayPlayback>())
+                    .in_set(OnUpdate(AppState::Game)),
+            )
+            // Paused, the arrow keys belong to the settings screen
+            .add_system(
+                control_playback
+                    .run_if(resource_exists::<ReplayPlayback>())
+                    .in_set(OnUpdate(AppState::Game))
+                    .in_set(OnUpdate(GameState::Running)),
+            )
+            .add_system(
+                advance_playback
+                    .after(seek_playback)
+                    .run_if(resource_exists::<ReplayPlayback>())
+                    .in_set(OnUpdate(AppState::Game))
+                    .in_set(OnUpdate(GameState::Running)),
+            );
+    }
+}
+
+// Run condition for systems that only care about runs being played, not watched.
+pub fn not_watching_replay(replay_playback: Option<Res<ReplayPlayback>>) -> bool {
+    replay_playback.is_none()
+}
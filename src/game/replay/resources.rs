@@ -0,0 +1,188 @@
+use super::REPLAYS_DIR;
+use crate::game::player::components::PlayerIntent;
+use crate::game::player::resources::MovementConfig;
+use crate::game::surface::resources::SurfaceConfig;
+use crate::game::GameMode;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+// What a single player did on a single tick.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct ReplayInput {
+    pub direction: [f32; 2],
+    pub dash: bool,
+    pub fire: Option<[f32; 2]>,
+}
+
+impl ReplayInput {
+    pub fn from_intent(intent: &PlayerIntent) -> Self {
+        ReplayInput {
+            direction: intent.direction.truncate().into(),
+            dash: intent.dash,
+            fire: intent.fire.map(|target| target.truncate().into()),
+        }
+    }
+
+    pub fn to_intent(self) -> PlayerIntent {
+        PlayerIntent {
+            direction: Vec2::from(self.direction).extend(0.0),
+            dash: self.dash,
+            fire: self.fire.map(|target| Vec2::from(target).extend(0.0)),
+        }
+    }
+}
+
+// Consecutive ticks on which every player kept doing the same thing.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InputRun {
+    pub ticks: u32,
+    // Indexed by the player id.
+    pub inputs: Vec<ReplayInput>,
+}
+
+// Where the first player was and how the run was going at the start of a tick.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct TrackPoint {
+    // None once the player is out.
+    pub position: Option<[f32; 2]>,
+    pub score: usize,
+}
+
+// Everything needed to play a run again: the seed, the rules and the inputs of every tick.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Replay {
+    pub seed: u64,
+    pub config_hash: u64,
+    pub game_mode: GameMode,
+    pub player_count: usize,
+    pub runs: Vec<InputRun>,
+    // One point per tick, so the run can be raced without simulating it.
+    pub track: Vec<TrackPoint>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, game_mode: GameMode, player_count: usize, config_hash: u64) -> Self {
+        Replay {
+            seed,
+            config_hash,
+            game_mode,
+            player_count,
+            runs: Vec::new(),
+            track: Vec::new(),
+        }
+    }
+
+    pub fn push_tick(&mut self, inputs: Vec<ReplayInput>) {
+        match self.runs.last_mut() {
+            Some(run) if run.inputs == inputs => run.ticks += 1,
+            _ => self.runs.push(InputRun { ticks: 1, inputs }),
+        }
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.runs.iter().map(|run| run.ticks as u64).sum()
+    }
+
+    pub fn save(&self, score: usize) -> bincode::Result<PathBuf> {
+        let path = replay_path(self.seed, score);
+        std::fs::create_dir_all(REPLAYS_DIR)?;
+        std::fs::write(&path, bincode::serialize(self)?)?;
+        Ok(path)
+    }
+
+    pub fn load(seed: u64, score: usize) -> bincode::Result<Self> {
+        let bytes = std::fs::read(replay_path(seed, score))?;
+        bincode::deserialize(&bytes)
+    }
+}
+
+// Runs racing a ghost share its seed, the score tells them apart.
+pub fn replay_path(seed: u64, score: usize) -> PathBuf {
+    PathBuf::from(REPLAYS_DIR).join(format!("{}_{}.replay", seed, score))
+}
+
+// Fingerprint of the settings the simulation depends on. A replay played with different
+// settings won't follow the recorded run.
+pub fn config_hash(
+    movement_config: &MovementConfig,
+    surface_config: &SurfaceConfig,
+    game_mode: GameMode,
+    player_count: usize,
+) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    format!("{:?}", movement_config).hash(&mut hasher);
+    // The surfaces are kept in a map, sorted so the hash doesn't depend on its order.
+    let mut surfaces: Vec<String> = surface_config
+        .surfaces
+        .iter()
+        .map(|surface| format!("{:?}", surface))
+        .collect();
+    surfaces.sort();
+    surfaces.hash(&mut hasher);
+    format!("{:?}", surface_config.regions).hash(&mut hasher);
+    surface_config.modify_movement.hash(&mut hasher);
+    format!("{:?}", game_mode).hash(&mut hasher);
+    player_count.hash(&mut hasher);
+    hasher.finish()
+}
+
+// The run being recorded, if any.
+#[derive(Resource, Default)]
+pub struct ReplayRecorder {
+    pub replay: Option<Replay>,
+}
+
+// High score whose run gets watched next, counted from the best one.
+#[derive(Resource, Default)]
+pub struct ReplaySelection {
+    pub index: usize,
+}
+
+// Present while a replay is being watched instead of played.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    pub ticks: u64,
+    pub speed: f32,
+    pub accumulator: f32,
+    // Tick to fast forward to.
+    pub seek_to: Option<u64>,
+    // Where the last input lookup ended, ticks are mostly played in order.
+    run: usize,
+    run_start: u64,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        ReplayPlayback {
+            ticks: replay.ticks(),
+            replay,
+            speed: 1.0,
+            accumulator: 0.0,
+            seek_to: None,
+            run: 0,
+            run_start: 0,
+        }
+    }
+
+    // Inputs recorded for the tick, none once the replay is over.
+    pub fn inputs_at(&mut self, tick: u64) -> Option<Vec<ReplayInput>> {
+        if tick < self.run_start {
+            self.run = 0;
+            self.run_start = 0;
+        }
+
+        while let Some(run) = self.replay.runs.get(self.run) {
+            if tick < self.run_start + run.ticks as u64 {
+                return Some(run.inputs.clone());
+            }
+            self.run_start += run.ticks as u64;
+            self.run += 1;
+        }
+        None
+    }
+}
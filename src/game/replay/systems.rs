@@ -0,0 +1,287 @@
+use super::resources::*;
+use super::{MAX_PLAYBACK_SPEED, MIN_PLAYBACK_SPEED, SCRUB_SECONDS};
+use crate::events::GameOver;
+use crate::game::player::components::{Player, PlayerId, PlayerIntent};
+use crate::game::player::resources::{MovementConfig, PlayerCount};
+use crate::game::save::resources::ResumedGame;
+use crate::game::score::resources::{HighScores, Score};
+use crate::game::simulation::resources::{GameRng, SimulationClock};
+use crate::game::simulation::step_simulation;
+use crate::game::simulation::{MAX_TICKS_PER_FRAME, SIMULATION_TICK_RATE};
+use crate::game::surface::resources::SurfaceConfig;
+use crate::game::GameMode;
+use crate::AppState;
+
+use bevy::prelude::*;
+
+#[allow(clippy::too_many_arguments)]
+pub fn start_recording(
+    mut replay_recorder: ResMut<ReplayRecorder>,
+    game_rng: Res<GameRng>,
+    game_mode: Res<GameMode>,
+    player_count: Res<PlayerCount>,
+    movement_config: Res<MovementConfig>,
+    surface_config: Res<SurfaceConfig>,
+    simulation_clock: Res<SimulationClock>,
+    resumed_game: Option<Res<ResumedGame>>,
+) {
+    // Runs stepped by something else, like a replay or a rollback session, aren't recorded.
+    // Neither are continued runs, they didn't start from the seed.
+    if simulation_clock.manual || resumed_game.is_some() {
+        replay_recorder.replay = None;
+        return;
+    }
+
+    replay_recorder.replay = Some(Replay::new(
+        game_rng.seed,
+        *game_mode,
+        player_count.value,
+        config_hash(
+            &movement_config,
+            &surface_config,
+            *game_mode,
+            player_count.value,
+        ),
+    ));
+}
+
+// Stores what every player does on this tick, and where the first player is.
+pub fn record_tick(
+    player_query: Query<(&Player, &PlayerIntent, &Transform)>,
+    mut replay_recorder: ResMut<ReplayRecorder>,
+    score: Res<Score>,
+) {
+    let replay = match replay_recorder.replay.as_mut() {
+        Some(v) => v,
+        None => return,
+    };
+
+    // Players that are out keep their slot, so the others don't change places.
+    let mut inputs = vec![ReplayInput::default(); replay.player_count];
+    for (player, intent, _) in player_query.iter() {
+        if inputs.len() <= player.id.0 {
+            inputs.resize(player.id.0 + 1, ReplayInput::default());
+        }
+        inputs[player.id.0] = ReplayInput::from_intent(intent);
+    }
+
+    replay.push_tick(inputs);
+    replay.track.push(TrackPoint {
+        position: player_query
+            .iter()
+            .find(|(player, _, _)| player.id == PlayerId(0))
+            .map(|(_, _, transform)| transform.translation.truncate().into()),
+        score: score.value,
+    });
+}
+
+pub fn save_replay(
+    mut game_over_event_reader: EventReader<GameOver>,
+    mut replay_recorder: ResMut<ReplayRecorder>,
+) {
+    let score_value = match game_over_event_reader.iter().next() {
+        Some(event) => event.score_value,
+        None => return,
+    };
+    let replay = match replay_recorder.replay.take() {
+        Some(v) => v,
+        None => return,
+    };
+
+    match replay.save(score_value) {
+        Ok(path) => println!("Replay saved to {}", path.display()),
+        Err(error) => println!("Could not save the replay: {}", error),
+    }
+}
+
+// Goes through the high scores, the selected one's run is the one to watch.
+pub fn select_replay(
+    keyboard_input: Res<Input<KeyCode>>,
+    high_scores: Res<HighScores>,
+    mut replay_selection: ResMut<ReplaySelection>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Tab) {
+        return;
+    }
+
+    let ranked = high_scores.ranked();
+    if ranked.is_empty() {
+        println!("No high scores to replay yet!");
+        return;
+    }
+
+    replay_selection.index = (replay_selection.index + 1) % ranked.len();
+    println!(
+        "Selected replay {}. {}",
+        replay_selection.index + 1,
+        ranked[replay_selection.index].describe()
+    );
+}
+
+// Watches the run behind the selected high score.
+#[allow(clippy::too_many_arguments)]
+pub fn play_selected_replay(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    high_scores: Res<HighScores>,
+    replay_selection: Res<ReplaySelection>,
+    movement_config: Res<MovementConfig>,
+    surface_config: Res<SurfaceConfig>,
+    mut game_mode: ResMut<GameMode>,
+    mut player_count: ResMut<PlayerCount>,
+    mut simulation_clock: ResMut<SimulationClock>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::R) {
+        return;
+    }
+
+    let ranked = high_scores.ranked();
+    let high_score = match ranked.get(replay_selection.index).or(ranked.first()) {
+        Some(v) => v,
+        None => {
+            println!("No high scores to replay yet!");
+            return;
+        }
+    };
+    let replay = match Replay::load(high_score.seed, high_score.score) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not load the replay: {}", error);
+            return;
+        }
+    };
+
+    if replay.config_hash
+        != config_hash(
+            &movement_config,
+            &surface_config,
+            replay.game_mode,
+            replay.player_count,
+        )
+    {
+        println!("The replay was recorded with a different config, it may not play out the same!");
+    }
+
+    println!(
+        "Watching {}'s run worth {} points",
+        high_score.name, high_score.score
+    );
+
+    *game_mode = replay.game_mode;
+    player_count.value = replay.player_count;
+    commands.insert_resource(GameRng::new(replay.seed));
+    simulation_clock.manual = true;
+    commands.insert_resource(ReplayPlayback::new(replay));
+    commands.insert_resource(NextState(Some(AppState::Game)));
+}
+
+pub fn rewind_replay(replay_playback: Res<ReplayPlayback>, mut game_rng: ResMut<GameRng>) {
+    // Leaving the game for good doesn't seek anywhere.
+    if replay_playback.seek_to.is_some() {
+        *game_rng = GameRng::new(replay_playback.replay.seed);
+    }
+}
+
+pub fn stop_playback(mut commands: Commands, mut simulation_clock: ResMut<SimulationClock>) {
+    commands.remove_resource::<ReplayPlayback>();
+    simulation_clock.manual = false;
+}
+
+// Brackets change the playback speed, arrows jump through the replay.
+pub fn control_playback(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut replay_playback: ResMut<ReplayPlayback>,
+    simulation_clock: Res<SimulationClock>,
+) {
+    if keyboard_input.just_pressed(KeyCode::LBracket) {
+        replay_playback.speed = (replay_playback.speed / 2.0).max(MIN_PLAYBACK_SPEED);
+        println!("Playback speed: {}x", replay_playback.speed);
+    }
+    if keyboard_input.just_pressed(KeyCode::RBracket) {
+        replay_playback.speed = (replay_playback.speed * 2.0).min(MAX_PLAYBACK_SPEED);
+        println!("Playback speed: {}x", replay_playback.speed);
+    }
+
+    let scrub_ticks = SCRUB_SECONDS * SIMULATION_TICK_RATE as u64;
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        let target = (simulation_clock.tick + scrub_ticks).min(replay_playback.ticks);
+        replay_playback.seek_to = Some(target);
+    }
+    // The past can't be simulated backwards, the run starts over and fast forwards instead.
+    if keyboard_input.just_pressed(KeyCode::Left) {
+        replay_playback.seek_to = Some(simulation_clock.tick.saturating_sub(scrub_ticks));
+        commands.insert_resource(NextState(Some(AppState::Game)));
+    }
+}
+
+// Silently simulates the ticks up to the one being sought.
+pub fn seek_playback(world: &mut World) {
+    world.resource_scope(|world, mut replay_playback: Mut<ReplayPlayback>| {
+        let target = match replay_playback.seek_to.take() {
+            Some(v) => v,
+            None => return,
+        };
+
+        while world.resource::<SimulationClock>().tick < target {
+            if !play_tick(world, &mut replay_playback, true) {
+                break;
+            }
+        }
+    });
+}
+
+// Plays the ticks due this frame at the playback speed.
+pub fn advance_playback(world: &mut World) {
+    let delta_seconds = world.resource::<Time>().delta_seconds();
+
+    world.resource_scope(|world, mut replay_playback: Mut<ReplayPlayback>| {
+        let tick_duration = 1.0 / SIMULATION_TICK_RATE as f32;
+        let max_ticks = (MAX_TICKS_PER_FRAME as f32 * replay_playback.speed).ceil() as usize;
+        replay_playback.accumulator += delta_seconds * replay_playback.speed;
+
+        let mut ticks = 0;
+        while replay_playback.accumulator >= tick_duration {
+            replay_playback.accumulator -= tick_duration;
+            ticks += 1;
+        }
+        if ticks > max_ticks {
+            ticks = max_ticks;
+            replay_playback.accumulator = 0.0;
+        }
+
+        // Sped up sounds would only pile up on each other.
+        let muted = replay_playback.speed > 1.0;
+        for _ in 0..ticks {
+            if !play_tick(world, &mut replay_playback, muted) {
+                println!("Replay finished!");
+                world.insert_resource(NextState(Some(AppState::Menu)));
+                return;
+            }
+        }
+    });
+}
+
+// Feeds the recorded inputs to the players and simulates the tick.
+// Returns false once the replay has no more ticks.
+fn play_tick(world: &mut World, replay_playback: &mut ReplayPlayback, muted: bool) -> bool {
+    let tick = world.resource::<SimulationClock>().tick;
+    let inputs = match replay_playback.inputs_at(tick) {
+        Some(v) => v,
+        None => return false,
+    };
+
+    for (player, mut intent) in world
+        .query::<(&Player, &mut PlayerIntent)>()
+        .iter_mut(world)
+    {
+        *intent = inputs
+            .get(player.id.0)
+            .copied()
+            .unwrap_or_default()
+            .to_intent();
+    }
+
+    step_simulation(world, muted);
+    true
+}
@@ -0,0 +1,40 @@
+pub mod resources;
+mod systems;
+
+use super::simulation::run_simulation;
+use super::GameMode;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const SAVE_PATH: &str = "savegame.ron";
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(save_and_quit.run_if(in_state(AppState::Game)))
+            .add_system(continue_saved_game.run_if(in_state(AppState::Menu)))
+            // The saved run replaces whatever the game state spawned on entering it
+            .add_system(
+                restore_saved_game
+                    .before(run_simulation)
+                    .run_if(resource_exists::<ResumedGame>())
+                    .in_set(OnUpdate(AppState::Game)),
+            )
+            .add_system(forget_saved_run.in_schedule(OnEnter(AppState::Menu)));
+    }
+}
+
+// Modes whose whole state fits in a save. Puzzle levels and versus matches can't be
+// picked up later, and the daily challenge is a single attempt.
+pub fn can_be_saved(game_mode: GameMode) -> bool {
+    matches!(game_mode, GameMode::Classic | GameMode::Shooter)
+}
+
+// Run condition for systems that only care about runs that are over, not put aside for later.
+pub fn run_not_saved(saved_run: Option<Res<SavedRun>>) -> bool {
+    saved_run.is_none()
+}
@@ -0,0 +1,277 @@
+use crate::game::boss::boss_bundle;
+use crate::game::boss::components::{Boss, BossPhase};
+use crate::game::enemy::components::Enemy;
+use crate::game::enemy::enemy_bundle;
+use crate::game::enemy::resources::{EnemyTimer, Wave};
+use crate::game::player::components::*;
+use crate::game::player::player_bundle;
+use crate::game::player::resources::PlayerCount;
+use crate::game::projectile::resources::Ammo;
+use crate::game::score::resources::{Combo, Score};
+use crate::game::simulation::resources::{GameRng, SimulationClock};
+use crate::game::star::components::Star;
+use crate::game::star::resources::StarTimer;
+use crate::game::star::star_bundle;
+use crate::game::GameMode;
+
+use bevy::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// How far along a timer is, so it goes off on the same tick it would have.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct SavedTimer {
+    pub duration: Duration,
+    pub elapsed: Duration,
+}
+
+impl SavedTimer {
+    pub fn new(timer: &Timer) -> Self {
+        SavedTimer {
+            duration: timer.duration(),
+            elapsed: timer.elapsed(),
+        }
+    }
+
+    pub fn to_timer(self, mode: TimerMode) -> Timer {
+        let mut timer = Timer::new(self.duration, mode);
+        timer.set_elapsed(self.elapsed);
+        timer
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SavedPlayer {
+    pub id: usize,
+    pub position: [f32; 3],
+    pub velocity: [f32; 3],
+    pub lives: usize,
+    pub dash_cooldown: SavedTimer,
+    pub invulnerable: Option<SavedTimer>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SavedEnemy {
+    pub position: [f32; 3],
+    pub direction: [f32; 3],
+}
+
+// The phase follows from the health, it's worked out again on the next tick.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SavedBoss {
+    pub position: [f32; 3],
+    pub health: usize,
+    pub direction: [f32; 3],
+    pub charge_timer: SavedTimer,
+    pub charging: bool,
+    pub minion_timer: SavedTimer,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SavedCombo {
+    pub count: usize,
+    pub best: usize,
+    pub timer: SavedTimer,
+}
+
+// A run put aside to be continued later, down to the state of the random number generator.
+// Projectiles in flight and the dashes in progress are gone in a blink, they aren't kept.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SavedGame {
+    pub game_mode: GameMode,
+    pub player_count: usize,
+    pub seed: u64,
+    pub rng: ChaCha8Rng,
+    pub tick: u64,
+    pub score: usize,
+    pub player_scores: Vec<usize>,
+    // Missing from the games saved before combos were simulated, those start without one.
+    #[serde(default)]
+    pub combo: Option<SavedCombo>,
+    pub wave: usize,
+    pub enemy_timer: SavedTimer,
+    pub star_timer: SavedTimer,
+    pub ammo: usize,
+    pub players: Vec<SavedPlayer>,
+    pub enemies: Vec<SavedEnemy>,
+    pub stars: Vec<[f32; 3]>,
+    pub bosses: Vec<SavedBoss>,
+}
+
+impl SavedGame {
+    pub fn save(world: &mut World) -> Self {
+        let players = world
+            .query::<(
+                &Player,
+                &Transform,
+                &Velocity,
+                &Lives,
+                &DashCooldown,
+                Option<&Invulnerable>,
+            )>()
+            .iter(world)
+            .map(
+                |(player, transform, velocity, lives, dash_cooldown, invulnerable)| SavedPlayer {
+                    id: player.id.0,
+                    position: transform.translation.to_array(),
+                    velocity: velocity.value.to_array(),
+                    lives: lives.value,
+                    dash_cooldown: SavedTimer::new(&dash_cooldown.timer),
+                    invulnerable: invulnerable
+                        .map(|invulnerable| SavedTimer::new(&invulnerable.timer)),
+                },
+            )
+            .collect();
+        let enemies = world
+            .query::<(&Transform, &Enemy)>()
+            .iter(world)
+            .map(|(transform, enemy)| SavedEnemy {
+                position: transform.translation.to_array(),
+                direction: enemy.direction.to_array(),
+            })
+            .collect();
+        let stars = world
+            .query_filtered::<&Transform, With<Star>>()
+            .iter(world)
+            .map(|transform| transform.translation.to_array())
+            .collect();
+        let bosses = world
+            .query::<(&Transform, &Boss)>()
+            .iter(world)
+            .map(|(transform, boss)| SavedBoss {
+                position: transform.translation.to_array(),
+                health: boss.health,
+                direction: boss.direction.to_array(),
+                charge_timer: SavedTimer::new(&boss.charge_timer),
+                charging: boss.charging,
+                minion_timer: SavedTimer::new(&boss.minion_timer),
+            })
+            .collect();
+
+        let game_rng = world.resource::<GameRng>();
+        let score = world.resource::<Score>();
+        let combo = world.resource::<Combo>();
+
+        SavedGame {
+            game_mode: *world.resource::<GameMode>(),
+            player_count: world.resource::<PlayerCount>().value,
+            seed: game_rng.seed,
+            rng: game_rng.rng.clone(),
+            tick: world.resource::<SimulationClock>().tick,
+            score: score.value,
+            player_scores: score.players.clone(),
+            combo: Some(SavedCombo {
+                count: combo.count,
+                best: combo.best,
+                timer: SavedTimer::new(&combo.timer),
+            }),
+            wave: world.resource::<Wave>().number,
+            enemy_timer: SavedTimer::new(&world.resource::<EnemyTimer>().timer),
+            star_timer: SavedTimer::new(&world.resource::<StarTimer>().timer),
+            ammo: world.resource::<Ammo>().value,
+            players,
+            enemies,
+            stars,
+            bosses,
+        }
+    }
+
+    pub fn restore(&self, world: &mut World) {
+        let mut simulation_clock = world.resource_mut::<SimulationClock>();
+        simulation_clock.tick = self.tick;
+        simulation_clock.accumulator = 0.0;
+        *world.resource_mut::<GameRng>() = GameRng {
+            seed: self.seed,
+            rng: self.rng.clone(),
+        };
+        *world.resource_mut::<Score>() = Score {
+            value: self.score,
+            players: self.player_scores.clone(),
+        };
+        *world.resource_mut::<Combo>() = match &self.combo {
+            Some(combo) => Combo {
+                count: combo.count,
+                best: combo.best,
+                timer: combo.timer.to_timer(TimerMode::Once),
+            },
+            None => Combo::default(),
+        };
+        world.resource_mut::<EnemyTimer>().timer = self.enemy_timer.to_timer(TimerMode::Repeating);
+        world.resource_mut::<StarTimer>().timer = self.star_timer.to_timer(TimerMode::Repeating);
+        world.resource_mut::<Ammo>().value = self.ammo;
+        // The boss shows up when the wave changes, the saved one is already in the list below.
+        world
+            .resource_mut::<Wave>()
+            .bypass_change_detection()
+            .number = self.wave;
+
+        let asset_server = world.resource::<AssetServer>().clone();
+
+        let entities: Vec<Entity> = world
+            .query_filtered::<Entity, Or<(With<Player>, With<Enemy>, With<Star>, With<Boss>)>>()
+            .iter(world)
+            .collect();
+        for entity in entities {
+            world.despawn(entity);
+        }
+
+        for player in self.players.iter() {
+            let mut player_entity = world.spawn(player_bundle(
+                PlayerId(player.id),
+                Vec3::from(player.position),
+                &asset_server,
+            ));
+            player_entity.insert((
+                Velocity {
+                    value: Vec3::from(player.velocity),
+                },
+                Lives {
+                    value: player.lives,
+                },
+                DashCooldown {
+                    timer: player.dash_cooldown.to_timer(TimerMode::Once),
+                },
+            ));
+            if let Some(invulnerable) = player.invulnerable {
+                player_entity.insert(Invulnerable {
+                    timer: invulnerable.to_timer(TimerMode::Once),
+                });
+            }
+        }
+        for enemy in self.enemies.iter() {
+            world.spawn(enemy_bundle(
+                Vec3::from(enemy.position),
+                Vec3::from(enemy.direction),
+                &asset_server,
+            ));
+        }
+        for position in self.stars.iter() {
+            world.spawn(star_bundle(Vec3::from(*position), &asset_server));
+        }
+        for boss in self.bosses.iter() {
+            world.spawn(boss_bundle(
+                Transform::from_translation(Vec3::from(boss.position)),
+                Boss {
+                    health: boss.health,
+                    phase: BossPhase::Chase,
+                    direction: Vec3::from(boss.direction),
+                    charge_timer: boss.charge_timer.to_timer(TimerMode::Once),
+                    charging: boss.charging,
+                    minion_timer: boss.minion_timer.to_timer(TimerMode::Repeating),
+                },
+                &asset_server,
+            ));
+        }
+    }
+}
+
+// The saved run being continued, restored on the first frame of the game state.
+#[derive(Resource)]
+pub struct ResumedGame {
+    pub saved_game: SavedGame,
+}
+
+// Present from saving a run until the menu is reached, the run isn't over yet.
+#[derive(Resource)]
+pub struct SavedRun {}
@@ -0,0 +1,101 @@
+use super::resources::*;
+use super::{can_be_saved, SAVE_PATH};
+use crate::game::player::components::Player;
+use crate::game::player::resources::PlayerCount;
+use crate::game::replay::resources::ReplayPlayback;
+use crate::game::GameMode;
+use crate::AppState;
+
+use bevy::prelude::*;
+
+// Puts the run aside and goes back to the menu, the run can be continued from there.
+pub fn save_and_quit(world: &mut World) {
+    if !world.resource::<Input<KeyCode>>().just_pressed(KeyCode::Q) {
+        return;
+    }
+    // Watched replays aren't the player's to save.
+    if world.contains_resource::<ReplayPlayback>() {
+        return;
+    }
+    // Everybody is out, the run is only waiting for the game over screen.
+    if world
+        .query_filtered::<(), With<Player>>()
+        .iter(world)
+        .next()
+        .is_none()
+    {
+        return;
+    }
+    let game_mode = *world.resource::<GameMode>();
+    if !can_be_saved(game_mode) {
+        println!("Runs in the {:?} mode can't be saved!", game_mode);
+        return;
+    }
+
+    let saved_game = SavedGame::save(world);
+    let saved_game = match ron::ser::to_string_pretty(&saved_game, default()) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not serialize the run: {}", error);
+            return;
+        }
+    };
+    if let Err(error) = std::fs::write(SAVE_PATH, saved_game) {
+        println!("Could not write {}: {}", SAVE_PATH, error);
+        return;
+    }
+
+    println!("Run saved, press C in the menu to continue it!");
+    world.insert_resource(SavedRun {});
+    world.insert_resource(NextState(Some(AppState::Menu)));
+}
+
+// Picks the saved run back up. The save is used up, the run can't be continued twice.
+pub fn continue_saved_game(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut game_mode: ResMut<GameMode>,
+    mut player_count: ResMut<PlayerCount>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::C) {
+        return;
+    }
+
+    let saved_game = match std::fs::read_to_string(SAVE_PATH) {
+        Ok(v) => v,
+        Err(_) => {
+            println!("No saved run to continue!");
+            return;
+        }
+    };
+    let saved_game = match ron::from_str::<SavedGame>(&saved_game) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not parse {}: {}", SAVE_PATH, error);
+            return;
+        }
+    };
+    if let Err(error) = std::fs::remove_file(SAVE_PATH) {
+        println!("Could not remove {}: {}", SAVE_PATH, error);
+    }
+
+    println!("Continuing the run worth {} points", saved_game.score);
+
+    *game_mode = saved_game.game_mode;
+    player_count.value = saved_game.player_count;
+    commands.insert_resource(ResumedGame { saved_game });
+    commands.insert_resource(NextState(Some(AppState::Game)));
+}
+
+pub fn restore_saved_game(world: &mut World) {
+    let resumed_game = match world.remove_resource::<ResumedGame>() {
+        Some(v) => v,
+        None => return,
+    };
+
+    resumed_game.saved_game.restore(world);
+}
+
+pub fn forget_saved_run(mut commands: Commands) {
+    commands.remove_resource::<SavedRun>();
+}
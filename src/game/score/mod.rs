@@ -1,21 +1,39 @@
 pub mod resources;
 mod systems;
 
+use super::player::check_star_collision;
+use super::replay::resources::ReplayPlayback;
+use super::simulation::SimulationSchedule;
 use crate::AppState;
 use resources::*;
 use systems::*;
 
 use bevy::prelude::*;
 
+pub const HIGH_SCORES_SAVE_PATH: &str = "high_scores.ron";
+pub const COMBO_WINDOW: f32 = 1.5; // Time to collect the next star and keep the combo going.
+pub const COMBO_STEP: usize = 5; // Every this many stars in a combo raise the multiplier by one.
+pub const MAX_COMBO_MULTIPLIER: usize = 4;
+
 pub struct ScorePlugin;
 
 impl Plugin for ScorePlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.init_resource::<Score>()
             .init_resource::<HighScores>()
+            .init_resource::<Combo>()
+            .add_startup_system(load_high_scores)
             .add_system(reset_scores.in_schedule(OnExit(AppState::Game)))
+            .add_system(reset_combo.in_schedule(OnExit(AppState::Game)))
+            // The combo window runs on the simulation clock, so it plays out the same in replays
+            .add_system(
+                update_combo
+                    .after(check_star_collision)
+                    .in_schedule(SimulationSchedule),
+            )
             .add_system(update_score)
-            .add_system(update_high_scores)
+            // A watched replay isn't a new run.
+            .add_system(update_high_scores.run_if(not(resource_exists::<ReplayPlayback>())))
             .add_system(print_high_scores);
     }
 }
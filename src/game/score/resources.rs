@@ -1,13 +1,81 @@
+use super::{COMBO_STEP, COMBO_WINDOW, MAX_COMBO_MULTIPLIER};
+use crate::events::PlayerResult;
+use crate::game::player::components::PlayerId;
+use crate::game::GameMode;
+
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
 
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Clone)]
 pub struct Score {
     pub value: usize,
+    // Points earned by every player, indexed by the player id.
+    pub players: Vec<usize>,
+}
+
+impl Score {
+    // Adds the points to the total and to the given player's score.
+    pub fn award(&mut self, player_id: PlayerId, points: usize) {
+        if self.players.len() <= player_id.0 {
+            self.players.resize(player_id.0 + 1, 0);
+        }
+
+        self.value += points;
+        self.players[player_id.0] += points;
+    }
+
+    // Takes up to the given points from the player, returns how many were actually taken.
+    pub fn deduct(&mut self, player_id: PlayerId, points: usize) -> usize {
+        let player_score = match self.players.get_mut(player_id.0) {
+            Some(v) => v,
+            None => return 0,
+        };
+        let deducted = points.min(*player_score);
+
+        *player_score -= deducted;
+        self.value -= deducted;
+        deducted
+    }
+
+    pub fn player_score(&self, player_id: PlayerId) -> usize {
+        self.players.get(player_id.0).copied().unwrap_or(0)
+    }
+
+    pub fn player_results(&self, player_count: usize) -> Vec<PlayerResult> {
+        (0..player_count)
+            .map(|index| PlayerResult {
+                id: PlayerId(index),
+                score: self.player_score(PlayerId(index)),
+            })
+            .collect()
+    }
 }
 
-#[derive(Resource, Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighScore {
+    pub name: String,
+    pub score: usize,
+    // Seed of the run, its replay is saved under it.
+    pub seed: u64,
+    pub game_mode: GameMode,
+    pub player_count: usize,
+}
+
+impl HighScore {
+    pub fn describe(&self) -> String {
+        format!(
+            "{} - {} points ({:?}, {} players)",
+            self.name, self.score, self.game_mode, self.player_count
+        )
+    }
+}
+
+// Saved between launches, so the runs behind them can be watched later on.
+#[derive(Resource, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct HighScores {
-    pub scores: Vec<(String, usize)>,
+    pub scores: Vec<HighScore>,
 }
 
 impl Default for HighScores {
@@ -15,3 +83,53 @@ impl Default for HighScores {
         HighScores { scores: Vec::new() }
     }
 }
+
+impl HighScores {
+    // Every high score, the best first.
+    pub fn ranked(&self) -> Vec<&HighScore> {
+        let mut ranked: Vec<&HighScore> = self.scores.iter().collect();
+        ranked.sort_by_key(|high_score| Reverse(high_score.score));
+        ranked
+    }
+
+    // Best run played with the given rules.
+    pub fn best_of(&self, game_mode: GameMode, player_count: usize) -> Option<&HighScore> {
+        self.scores
+            .iter()
+            .filter(|high_score| {
+                high_score.game_mode == game_mode && high_score.player_count == player_count
+            })
+            .max_by_key(|high_score| high_score.score)
+    }
+}
+
+// Stars collected in a quick succession.
+#[derive(Resource, Clone)]
+pub struct Combo {
+    pub count: usize,
+    // Longest combo of the run.
+    pub best: usize,
+    // Runs out when no star gets collected in time, ending the combo.
+    pub timer: Timer,
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        Combo {
+            count: 0,
+            best: 0,
+            timer: Timer::from_seconds(COMBO_WINDOW, TimerMode::Once),
+        }
+    }
+}
+
+impl Combo {
+    // What the next star's points are multiplied by. A combo that ran out
+    // counts for nothing, even before it gets reset.
+    pub fn multiplier(&self) -> usize {
+        if self.timer.finished() {
+            return 1;
+        }
+        (1 + self.count / COMBO_STEP).min(MAX_COMBO_MULTIPLIER)
+    }
+}
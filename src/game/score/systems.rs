@@ -1,7 +1,10 @@
 use super::resources::*;
+use super::HIGH_SCORES_SAVE_PATH;
 use bevy::prelude::*;
 
 use crate::events::*;
+use crate::game::camera::{BIG_COMBO, COMBO_ZOOM, MAX_COMBO_ZOOM};
+use crate::game::GameMode;
 
 // Prints the score whenever it has changed.
 pub fn update_score(score: Res<Score>) {
@@ -10,24 +13,96 @@ pub fn update_score(score: Res<Score>) {
     }
 }
 
+// Reads the high scores, there are none before the first launch.
+pub fn load_high_scores(mut high_scores: ResMut<HighScores>) {
+    let scores = match std::fs::read_to_string(HIGH_SCORES_SAVE_PATH) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    match ron::from_str::<HighScores>(&scores) {
+        Ok(v) => *high_scores = v,
+        Err(error) => println!("Could not parse {}: {}", HIGH_SCORES_SAVE_PATH, error),
+    }
+}
+
+fn save_high_scores(high_scores: &HighScores) {
+    let scores = match ron::ser::to_string_pretty(high_scores, default()) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not serialize the high scores: {}", error);
+            return;
+        }
+    };
+
+    if let Err(error) = std::fs::write(HIGH_SCORES_SAVE_PATH, scores) {
+        println!("Could not write {}: {}", HIGH_SCORES_SAVE_PATH, error);
+    }
+}
+
 pub fn update_high_scores(
     mut game_over_event_reader: EventReader<GameOver>,
     mut high_scores: ResMut<HighScores>,
+    game_mode: Res<GameMode>,
 ) {
     for event in game_over_event_reader.iter() {
-        high_scores
-            .scores
-            .push(("Player".to_string(), event.score_value));
+        high_scores.scores.push(HighScore {
+            name: "Player".to_string(),
+            score: event.score_value,
+            seed: event.seed,
+            game_mode: *game_mode,
+            player_count: event.players.len(),
+        });
+        save_high_scores(&high_scores);
     }
 }
 
-// If the high scores got changed, then print them.
+// If the high scores got changed, then print them, the best first.
 pub fn print_high_scores(high_scores: Res<HighScores>) {
-    if high_scores.is_changed() {
-        println!("High scores: {:?}", high_scores);
+    if !high_scores.is_changed() {
+        return;
+    }
+
+    println!("High scores:");
+    for (index, high_score) in high_scores.ranked().iter().enumerate() {
+        println!("{}. {}", index + 1, high_score.describe());
     }
 }
 
 pub fn reset_scores(mut score: ResMut<Score>) {
     score.value = 0;
+    score.players.clear();
+}
+
+pub fn update_combo(
+    mut star_collected_event_reader: EventReader<StarCollected>,
+    mut combo: ResMut<Combo>,
+    mut camera_effect_event_writer: EventWriter<CameraEffect>,
+    time: Res<Time>,
+) {
+    combo.timer.tick(time.delta());
+    if combo.timer.finished() {
+        combo.count = 0;
+    }
+
+    let stars_collected = star_collected_event_reader.iter().count();
+    if stars_collected == 0 {
+        return;
+    }
+
+    let previous_count = combo.count;
+    combo.count += stars_collected;
+    combo.best = combo.best.max(combo.count);
+    combo.timer.reset();
+
+    // Big combos punch the camera in, harder the bigger they get.
+    let milestone = combo.count / BIG_COMBO;
+    if milestone > previous_count / BIG_COMBO {
+        let zoom = (milestone as f32 * COMBO_ZOOM).min(MAX_COMBO_ZOOM);
+        camera_effect_event_writer.send(CameraEffect::ZoomPunch(zoom));
+    }
+}
+
+pub fn reset_combo(mut combo: ResMut<Combo>) {
+    *combo = Combo::default();
 }
@@ -0,0 +1,74 @@
+pub mod resources;
+mod systems;
+
+use super::camera::not_in_hit_stop;
+use super::GameState;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+pub use systems::{reseed_rng, run_simulation, step_simulation};
+
+use bevy::ecs::schedule::{ExecutorKind, ScheduleLabel, SystemSetConfigs};
+use bevy::prelude::*;
+
+pub const SIMULATION_TICK_RATE: u32 = 60;
+pub const MAX_TICKS_PER_FRAME: usize = 4; // Slow frames don't snowball into even slower ones.
+
+// Holds the gameplay systems. Every run of the schedule advances the game by exactly one tick,
+// so the same seed and inputs always lead to the same game.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SimulationSchedule;
+
+// Systems drawing from the game RNG. They run one after another in this order, so the
+// numbers each of them gets never depend on how the systems happened to be scheduled.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RngSet {
+    Level,
+    Stars,
+    Enemies,
+    Minions,
+}
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameRng>()
+            .init_resource::<SimulationClock>()
+            .edit_schedule(SimulationSchedule, |schedule| {
+                schedule
+                    .set_executor_kind(ExecutorKind::SingleThreaded)
+                    .configure_sets(rng_sets());
+            })
+            .edit_schedule(OnEnter(AppState::Game), |schedule| {
+                schedule.configure_sets(rng_sets());
+            })
+            // Upon leaving the game state, roll a new seed and start the clock over for the next run
+            .add_system(reseed_rng.in_schedule(OnExit(AppState::Game)))
+            .add_system(reset_clock.in_schedule(OnExit(AppState::Game)))
+            .add_system(
+                run_simulation
+                    .run_if(driven_by_frames)
+                    .run_if(not_in_hit_stop)
+                    .in_set(OnUpdate(AppState::Game))
+                    .in_set(OnUpdate(GameState::Running)),
+            );
+    }
+}
+
+fn rng_sets() -> SystemSetConfigs {
+    (
+        RngSet::Level,
+        RngSet::Stars,
+        RngSet::Enemies,
+        RngSet::Minions,
+    )
+        .chain()
+}
+
+// Run condition telling whether the simulation follows the frame time, or is stepped
+// by something else like a rollback session.
+pub fn driven_by_frames(simulation_clock: Res<SimulationClock>) -> bool {
+    !simulation_clock.manual
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use bevy::utils::Instant;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+// The only source of randomness for the gameplay. Seeded per run, so runs can be reproduced.
+#[derive(Resource, Clone)]
+pub struct GameRng {
+    pub seed: u64,
+    pub rng: ChaCha8Rng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        GameRng {
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng::new(random())
+    }
+}
+
+#[derive(Resource)]
+pub struct SimulationClock {
+    // Ticks simulated since the run started.
+    pub tick: u64,
+    // Frame time not yet turned into ticks.
+    pub accumulator: f32,
+    // Moment the simulation time is counted from, the ticks never look at the real clock.
+    pub start: Instant,
+    // Set when the ticks are stepped manually instead of following the frame time.
+    pub manual: bool,
+}
+
+impl Default for SimulationClock {
+    fn default() -> Self {
+        SimulationClock {
+            tick: 0,
+            accumulator: 0.0,
+            start: Instant::now(),
+            manual: false,
+        }
+    }
+}
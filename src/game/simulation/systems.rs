@@ -0,0 +1,81 @@
+use super::resources::*;
+use super::{SimulationSchedule, MAX_TICKS_PER_FRAME, SIMULATION_TICK_RATE};
+use crate::events::{CameraEffect, ParticleBurst, SoundEvent};
+
+use bevy::prelude::*;
+use std::time::Duration;
+
+// Turns the frame time into fixed simulation ticks.
+pub fn run_simulation(world: &mut World) {
+    let tick_duration = 1.0 / SIMULATION_TICK_RATE as f32;
+    let delta_seconds = world.resource::<Time>().delta_seconds();
+
+    let mut simulation_clock = world.resource_mut::<SimulationClock>();
+    simulation_clock.accumulator += delta_seconds;
+
+    let mut ticks = 0;
+    while simulation_clock.accumulator >= tick_duration {
+        simulation_clock.accumulator -= tick_duration;
+        ticks += 1;
+    }
+    if ticks > MAX_TICKS_PER_FRAME {
+        ticks = MAX_TICKS_PER_FRAME;
+        simulation_clock.accumulator = 0.0;
+    }
+
+    for _ in 0..ticks {
+        step_simulation(world, false);
+    }
+}
+
+// Runs the gameplay systems for one tick. The systems see a clock that advanced by exactly
+// one tick. Muted steps replay ticks that already happened once, so they make no sound.
+pub fn step_simulation(world: &mut World, muted: bool) {
+    let simulation_clock = world.resource::<SimulationClock>();
+    let tick_duration = Duration::from_secs(1) / SIMULATION_TICK_RATE;
+    let start = simulation_clock.start;
+    let tick = simulation_clock.tick as u32;
+
+    let mut simulation_time = Time::new(start);
+    simulation_time.update_with_instant(start + tick_duration * tick);
+    simulation_time.update_with_instant(start + tick_duration * (tick + 1));
+
+    let frame_time = world.remove_resource::<Time>();
+    world.insert_resource(simulation_time);
+    // The sounds, particles and camera effects asked for on throwaway events never show up.
+    let sound_events = muted.then(|| swap_events::<SoundEvent>(world)).flatten();
+    let particle_burst_events = muted.then(|| swap_events::<ParticleBurst>(world)).flatten();
+    let camera_effect_events = muted.then(|| swap_events::<CameraEffect>(world)).flatten();
+
+    world.run_schedule(SimulationSchedule);
+
+    if let Some(frame_time) = frame_time {
+        world.insert_resource(frame_time);
+    }
+    if let Some(sound_events) = sound_events {
+        world.insert_resource(sound_events);
+    }
+    if let Some(particle_burst_events) = particle_burst_events {
+        world.insert_resource(particle_burst_events);
+    }
+    if let Some(camera_effect_events) = camera_effect_events {
+        world.insert_resource(camera_effect_events);
+    }
+    world.resource_mut::<SimulationClock>().tick += 1;
+}
+
+// Puts fresh events in place of the current ones, handing the current ones back.
+fn swap_events<T: Event>(world: &mut World) -> Option<Events<T>> {
+    let events = world.remove_resource::<Events<T>>();
+    world.insert_resource(Events::<T>::default());
+    events
+}
+
+pub fn reseed_rng(mut game_rng: ResMut<GameRng>) {
+    *game_rng = GameRng::default();
+}
+
+pub fn reset_clock(mut simulation_clock: ResMut<SimulationClock>) {
+    simulation_clock.tick = 0;
+    simulation_clock.accumulator = 0.0;
+}
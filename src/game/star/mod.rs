@@ -1,29 +1,41 @@
 pub mod components;
-mod resources;
+pub mod resources;
 mod systems;
 
-use super::GameState;
+use super::simulation::{RngSet, SimulationSchedule};
 use crate::AppState;
 use resources::*;
 use systems::*;
 
+pub use systems::star_bundle;
+
 use bevy::prelude::*;
 
 pub const STARS_NUM: usize = 10;
 pub const STAR_SPAWN_PERIOD: f32 = 1.0;
 pub const STAR_SIZE: f32 = 30.0;
+pub const STAR_SCORE: usize = 1; // Points for collecting a star.
 
 pub struct StarPlugin;
 
 impl Plugin for StarPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<StarTimer>()
-            .add_system(spawn_star.in_schedule(OnEnter(AppState::Game)))
+            .add_system(
+                spawn_star
+                    .in_set(RngSet::Stars)
+                    .in_schedule(OnEnter(AppState::Game)),
+            )
             .add_system(despawn_stars.in_schedule(OnExit(AppState::Game)))
+            .add_system(reset_star_timer.in_schedule(OnExit(AppState::Game)))
             .add_systems(
-                (spawn_stars_over_time, star_timer_tick)
-                    .in_set(OnUpdate(AppState::Game))
-                    .in_set(OnUpdate(GameState::Running)),
+                (
+                    spawn_stars_over_time
+                        .after(star_timer_tick)
+                        .in_set(RngSet::Stars),
+                    star_timer_tick,
+                )
+                    .in_schedule(SimulationSchedule),
             );
     }
 }
@@ -1,63 +1,61 @@
 use super::components::*;
 use super::resources::*;
 use super::STARS_NUM;
+use crate::game::simulation::resources::GameRng;
+use crate::game::{ARENA_HEIGHT, ARENA_WIDTH};
 
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 use rand::prelude::*;
 
 pub fn spawn_star(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
     asset_server: Res<AssetServer>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let primary_window = window_query.get_single().unwrap();
-
     for _ in 0..STARS_NUM {
-        let x_pos = random::<f32>() * primary_window.width();
-        let y_pos = random::<f32>() * primary_window.height();
-
-        commands.spawn((
-            SpriteBundle {
-                transform: Transform::from_xyz(x_pos, y_pos, 0.0),
-                texture: asset_server.load("sprites/Default/star.png"),
-                ..default()
-            },
-            Star {},
-        ));
+        let x_pos = game_rng.rng.gen::<f32>() * ARENA_WIDTH;
+        let y_pos = game_rng.rng.gen::<f32>() * ARENA_HEIGHT;
+
+        commands.spawn(star_bundle(Vec3::new(x_pos, y_pos, 0.0), &asset_server));
     }
 }
 
 // Unlike spawn_star does not creates STARS_NUM of stars, but instead periodicall spawns them.
 pub fn spawn_stars_over_time(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
     asset_server: Res<AssetServer>,
     star_timer: Res<StarTimer>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let primary_window = window_query.get_single().unwrap();
-
     if star_timer.timer.just_finished() {
-        let x_pos = random::<f32>() * primary_window.width();
-        let y_pos = random::<f32>() * primary_window.height();
-
-        commands.spawn((
-            SpriteBundle {
-                transform: Transform::from_xyz(x_pos, y_pos, 0.0),
-                texture: asset_server.load("sprites/Default/star.png"),
-                ..default()
-            },
-            Star {},
-        ));
+        let x_pos = game_rng.rng.gen::<f32>() * ARENA_WIDTH;
+        let y_pos = game_rng.rng.gen::<f32>() * ARENA_HEIGHT;
+
+        commands.spawn(star_bundle(Vec3::new(x_pos, y_pos, 0.0), &asset_server));
     }
 }
 
+pub fn star_bundle(position: Vec3, asset_server: &AssetServer) -> (SpriteBundle, Star) {
+    (
+        SpriteBundle {
+            transform: Transform::from_translation(position),
+            texture: asset_server.load("sprites/Default/star.png"),
+            ..default()
+        },
+        Star {},
+    )
+}
+
 pub fn despawn_stars(mut commands: Commands, star_query: Query<Entity, With<Star>>) {
     for star_entity in star_query.iter() {
         commands.entity(star_entity).despawn();
     }
 }
 
+pub fn reset_star_timer(mut star_timer: ResMut<StarTimer>) {
+    *star_timer = StarTimer::default();
+}
+
 pub fn star_timer_tick(mut star_timer: ResMut<StarTimer>, time: Res<Time>) {
     star_timer.timer.tick(time.delta());
 }
@@ -0,0 +1,42 @@
+pub mod resources;
+mod systems;
+
+use super::replay::not_watching_replay;
+use super::save::run_not_saved;
+use super::simulation::run_simulation;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const STATS_SAVE_PATH: &str = "stats.ron";
+pub const RECENT_RUNS_NUM: usize = 20;
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LifetimeStats>()
+            .init_resource::<RunStats>()
+            .add_startup_system(load_lifetime_stats)
+            .add_system(reset_run_stats.in_schedule(OnEnter(AppState::Game)))
+            // Runs count as soon as they're left, finished or not. Watched replays don't count,
+            // and saved runs only count once they're over.
+            .add_system(
+                record_run
+                    .run_if(not_watching_replay)
+                    .run_if(run_not_saved)
+                    .in_schedule(OnExit(AppState::Game)),
+            )
+            // The last ticks of a run happen on its last frame in the game state.
+            .add_systems(
+                (
+                    track_run_stats.after(run_simulation),
+                    track_deaths.after(run_simulation),
+                )
+                    .in_set(OnUpdate(AppState::Game))
+                    .distributive_run_if(not_watching_replay),
+            );
+    }
+}
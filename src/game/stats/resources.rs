@@ -0,0 +1,61 @@
+use super::RECENT_RUNS_NUM;
+use crate::events::HitSource;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+// Statistics over every run ever played, saved between launches.
+#[derive(Resource, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifetimeStats {
+    pub runs: usize,
+    pub stars: usize,
+    // In seconds, like the survival times.
+    pub play_time: f32,
+    pub deaths: BTreeMap<HitSource, usize>,
+    pub longest_survival: f32,
+    pub total_score: usize,
+    pub best_combo: usize,
+    // Scores of the latest runs, the oldest first.
+    pub recent_scores: VecDeque<usize>,
+}
+
+impl LifetimeStats {
+    pub fn average_score(&self) -> f32 {
+        if self.runs == 0 {
+            return 0.0;
+        }
+        self.total_score as f32 / self.runs as f32
+    }
+
+    pub fn average_survival(&self) -> f32 {
+        if self.runs == 0 {
+            return 0.0;
+        }
+        self.play_time / self.runs as f32
+    }
+
+    // Adds up the finished run.
+    pub fn record_run(&mut self, run_stats: &RunStats) {
+        self.runs += 1;
+        self.play_time += run_stats.seconds;
+        self.longest_survival = self.longest_survival.max(run_stats.seconds);
+        self.total_score += run_stats.score;
+        self.best_combo = self.best_combo.max(run_stats.best_combo);
+
+        self.recent_scores.push_back(run_stats.score);
+        while self.recent_scores.len() > RECENT_RUNS_NUM {
+            self.recent_scores.pop_front();
+        }
+    }
+}
+
+// How the current run is going. Kept up to date while playing, since the game resources
+// are reset by the time the run gets recorded.
+#[derive(Resource, Debug, Default)]
+pub struct RunStats {
+    pub seconds: f32,
+    pub score: usize,
+    pub best_combo: usize,
+}
@@ -0,0 +1,71 @@
+use super::resources::*;
+use super::STATS_SAVE_PATH;
+use crate::events::{PlayerOut, StarCollected};
+use crate::game::score::resources::{Combo, Score};
+use crate::game::simulation::resources::SimulationClock;
+use crate::game::simulation::SIMULATION_TICK_RATE;
+
+use bevy::prelude::*;
+
+// Reads the stats, there are none before the first launch.
+pub fn load_lifetime_stats(mut lifetime_stats: ResMut<LifetimeStats>) {
+    let stats = match std::fs::read_to_string(STATS_SAVE_PATH) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    match ron::from_str::<LifetimeStats>(&stats) {
+        Ok(v) => *lifetime_stats = v,
+        Err(error) => println!("Could not parse {}: {}", STATS_SAVE_PATH, error),
+    }
+}
+
+fn save_lifetime_stats(lifetime_stats: &LifetimeStats) {
+    let stats = match ron::ser::to_string_pretty(lifetime_stats, default()) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not serialize the stats: {}", error);
+            return;
+        }
+    };
+
+    if let Err(error) = std::fs::write(STATS_SAVE_PATH, stats) {
+        println!("Could not write {}: {}", STATS_SAVE_PATH, error);
+    }
+}
+
+pub fn reset_run_stats(mut run_stats: ResMut<RunStats>) {
+    *run_stats = RunStats::default();
+}
+
+pub fn track_run_stats(
+    mut star_collected_event_reader: EventReader<StarCollected>,
+    mut run_stats: ResMut<RunStats>,
+    mut lifetime_stats: ResMut<LifetimeStats>,
+    score: Res<Score>,
+    combo: Res<Combo>,
+    simulation_clock: Res<SimulationClock>,
+) {
+    let stars_collected = star_collected_event_reader.iter().count();
+    if stars_collected > 0 {
+        lifetime_stats.stars += stars_collected;
+    }
+
+    run_stats.seconds = simulation_clock.tick as f32 / SIMULATION_TICK_RATE as f32;
+    run_stats.score = score.value;
+    run_stats.best_combo = combo.best;
+}
+
+pub fn track_deaths(
+    mut player_out_event_reader: EventReader<PlayerOut>,
+    mut lifetime_stats: ResMut<LifetimeStats>,
+) {
+    for event in player_out_event_reader.iter() {
+        *lifetime_stats.deaths.entry(event.source).or_insert(0) += 1;
+    }
+}
+
+pub fn record_run(mut lifetime_stats: ResMut<LifetimeStats>, run_stats: Res<RunStats>) {
+    lifetime_stats.record_run(&run_stats);
+    save_lifetime_stats(&lifetime_stats);
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+// What the floor is made of, it decides the footstep sounds and how the players move on it.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash, Deserialize)]
+pub enum Surface {
+    #[default]
+    Concrete,
+    Wood,
+    Carpet,
+    Grass,
+    Snow,
+}
+
+// Patch of the arena floor covered by the given surface.
+#[derive(Component)]
+pub struct FloorRegion {
+    pub surface: Surface,
+    pub size: Vec2,
+}
+
+impl FloorRegion {
+    pub fn contains(&self, region_translation: Vec3, position: Vec3) -> bool {
+        let offset = (position - region_translation).truncate().abs();
+        offset.x <= self.size.x / 2.0 && offset.y <= self.size.y / 2.0
+    }
+}
@@ -0,0 +1,32 @@
+pub mod components;
+pub mod resources;
+mod systems;
+
+use super::player::move_player;
+use super::simulation::SimulationSchedule;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const SURFACES_CONFIG_PATH: &str = "assets/config/surfaces.ron";
+pub const DEFAULT_STEP_LENGTH: f32 = 80.0; // Distance walked between two footsteps.
+pub const FLOOR_Z: f32 = -2.0; // Below the ghost and everything else.
+
+pub struct SurfacePlugin;
+
+impl Plugin for SurfacePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SurfaceConfig>()
+            .add_startup_system(load_surface_config)
+            // Upon entering the game state, lay out the floor
+            .add_system(spawn_floor_regions.in_schedule(OnEnter(AppState::Game)))
+            .add_system(despawn_floor_regions.in_schedule(OnExit(AppState::Game)))
+            .add_system(
+                update_ground
+                    .before(move_player)
+                    .in_schedule(SimulationSchedule),
+            );
+    }
+}
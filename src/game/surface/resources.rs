@@ -0,0 +1,54 @@
+use super::components::Surface;
+use super::DEFAULT_STEP_LENGTH;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SurfaceProperties {
+    pub color: (f32, f32, f32, f32),
+    // Multiplies the player's top speed.
+    pub speed: f32,
+    // Multiplies the friction of the inertial movement model, low friction makes players slide.
+    pub friction: f32,
+    // Distance walked between two footsteps.
+    pub step_length: f32,
+}
+
+impl Default for SurfaceProperties {
+    fn default() -> Self {
+        SurfaceProperties {
+            color: (0.0, 0.0, 0.0, 0.0),
+            speed: 1.0,
+            friction: 1.0,
+            step_length: DEFAULT_STEP_LENGTH,
+        }
+    }
+}
+
+// Rectangle of the arena covered by a surface, in fractions of the arena size.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegionDefinition {
+    pub surface: Surface,
+    pub min: (f32, f32),
+    pub max: (f32, f32),
+}
+
+// Floor of the arena, loaded from SURFACES_CONFIG_PATH at startup.
+#[derive(Resource, Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SurfaceConfig {
+    // Whether the surfaces change the movement, or only the sound of it.
+    pub modify_movement: bool,
+    pub surfaces: HashMap<Surface, SurfaceProperties>,
+    // Later regions cover the earlier ones. The rest of the floor is concrete.
+    pub regions: Vec<RegionDefinition>,
+}
+
+impl SurfaceConfig {
+    pub fn properties(&self, surface: Surface) -> SurfaceProperties {
+        self.surfaces.get(&surface).cloned().unwrap_or_default()
+    }
+}
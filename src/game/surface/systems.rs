@@ -0,0 +1,92 @@
+use super::components::*;
+use super::resources::*;
+use super::{FLOOR_Z, SURFACES_CONFIG_PATH};
+use crate::game::player::components::{Ground, Player};
+use crate::game::{ARENA_HEIGHT, ARENA_WIDTH};
+
+use bevy::prelude::*;
+
+// Reads the floor layout, the whole floor stays concrete without it.
+pub fn load_surface_config(mut surface_config: ResMut<SurfaceConfig>) {
+    let config = match std::fs::read_to_string(SURFACES_CONFIG_PATH) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not read {}: {}", SURFACES_CONFIG_PATH, error);
+            return;
+        }
+    };
+
+    match ron::from_str::<SurfaceConfig>(&config) {
+        Ok(v) => *surface_config = v,
+        Err(error) => println!("Could not parse {}: {}", SURFACES_CONFIG_PATH, error),
+    }
+}
+
+pub fn spawn_floor_regions(mut commands: Commands, surface_config: Res<SurfaceConfig>) {
+    let arena_size = Vec2::new(ARENA_WIDTH, ARENA_HEIGHT);
+
+    // Later regions are drawn on top of the earlier ones.
+    for (index, region) in surface_config.regions.iter().enumerate() {
+        let min = Vec2::from(region.min) * arena_size;
+        let max = Vec2::from(region.max) * arena_size;
+        let size = max - min;
+        let (r, g, b, a) = surface_config.properties(region.surface).color;
+
+        commands.spawn((
+            SpriteBundle {
+                transform: Transform::from_translation(
+                    ((min + max) / 2.0).extend(FLOOR_Z + index as f32 * 0.01),
+                ),
+                sprite: Sprite {
+                    color: Color::rgba(r, g, b, a),
+                    custom_size: Some(size),
+                    ..default()
+                },
+                ..default()
+            },
+            FloorRegion {
+                surface: region.surface,
+                size,
+            },
+        ));
+    }
+}
+
+pub fn despawn_floor_regions(
+    mut commands: Commands,
+    region_query: Query<Entity, With<FloorRegion>>,
+) {
+    for region_entity in region_query.iter() {
+        commands.entity(region_entity).despawn();
+    }
+}
+
+// Finds out what every player is standing on.
+pub fn update_ground(
+    mut player_query: Query<(&Transform, &mut Ground), With<Player>>,
+    region_query: Query<(&Transform, &FloorRegion)>,
+    surface_config: Res<SurfaceConfig>,
+) {
+    for (player_transform, mut ground) in player_query.iter_mut() {
+        // The topmost region under the player wins.
+        let surface = region_query
+            .iter()
+            .filter(|(region_transform, region)| {
+                region.contains(region_transform.translation, player_transform.translation)
+            })
+            .max_by(|(a, _), (b, _)| a.translation.z.total_cmp(&b.translation.z))
+            .map_or(Surface::default(), |(_, region)| region.surface);
+
+        if ground.surface == surface {
+            continue;
+        }
+
+        let properties = surface_config.properties(surface);
+        ground.surface = surface;
+        ground.step_length = properties.step_length;
+        if surface_config.modify_movement {
+            ground.speed = properties.speed;
+            ground.friction = properties.friction;
+        }
+    }
+}
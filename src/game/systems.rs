@@ -1,7 +1,12 @@
-use super::GameState;
+use super::player::resources::PlayerCount;
+use super::{GameMode, GameState};
 
 use bevy::prelude::*;
 
+pub fn resume_game(mut commands: Commands) {
+    commands.insert_resource(NextState(Some(GameState::Running)));
+}
+
 pub fn toggle_game(
     mut commands: Commands,
     keyboard_input: Res<Input<KeyCode>>,
@@ -20,3 +25,38 @@ pub fn toggle_game(
         println!("Game running!");
     }
 }
+
+// Lets the player pick the game mode while in the menu.
+pub fn select_game_mode(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut game_mode: ResMut<GameMode>,
+    mut player_count: ResMut<PlayerCount>,
+) {
+    let selected_mode = if keyboard_input.just_pressed(KeyCode::Key1) {
+        GameMode::Classic
+    } else if keyboard_input.just_pressed(KeyCode::Key2) {
+        GameMode::Puzzle
+    } else if keyboard_input.just_pressed(KeyCode::Key3) {
+        GameMode::Shooter
+    } else if keyboard_input.just_pressed(KeyCode::Key4) {
+        GameMode::Versus
+    } else if keyboard_input.just_pressed(KeyCode::Key5) {
+        GameMode::Daily
+    } else {
+        return;
+    };
+
+    if *game_mode != selected_mode {
+        *game_mode = selected_mode;
+        println!("Selected game mode: {:?}", selected_mode);
+    }
+
+    let player_range = game_mode.player_range();
+    let allowed_count = player_count
+        .value
+        .clamp(*player_range.start(), *player_range.end());
+    if player_count.value != allowed_count {
+        player_count.value = allowed_count;
+        println!("Players: {}", player_count.value);
+    }
+}
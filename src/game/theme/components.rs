@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+// Tile of the arena's background.
+#[derive(Component)]
+pub struct BackgroundTile {}
+
+// Sprite shown in its theme variant. The original texture is kept, so another theme
+// can replace it with its own variant.
+#[derive(Component)]
+pub struct ThemedSprite {
+    pub original: Handle<Image>,
+    pub texture: Handle<Image>,
+}
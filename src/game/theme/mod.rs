@@ -0,0 +1,36 @@
+pub mod components;
+pub mod resources;
+mod systems;
+
+use super::simulation::run_simulation;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const THEMES_CONFIG_PATH: &str = "assets/config/themes.ron";
+pub const THEMES_DIR: &str = "assets/themes"; // Holds a manifest for every theme.
+pub const BACKGROUND_Z: f32 = -3.0; // Below the floor regions.
+
+// Dresses the arena up in the theme of the current level or wave: the background,
+// the sprites and the sounds. Only the looks change, the game plays the same.
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ThemeLibrary>()
+            .init_resource::<ActiveTheme>()
+            .add_startup_system(load_themes)
+            .add_systems(
+                (
+                    select_theme.after(run_simulation),
+                    spawn_background.after(select_theme),
+                    apply_theme_sprites.after(select_theme),
+                )
+                    .in_set(OnUpdate(AppState::Game)),
+            )
+            // Upon leaving the game state, put the default looks back
+            .add_system(clear_theme.in_schedule(OnExit(AppState::Game)));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::events::Sound;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+// Which themes the arena goes through, loaded from THEMES_CONFIG_PATH at startup.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ThemesConfig {
+    // Names of the theme manifests in THEMES_DIR, in the order they come up.
+    pub order: Vec<String>,
+    // Waves played in a theme before moving on to the next one.
+    pub waves_per_theme: usize,
+    // Themes picked for particular puzzle levels, counted from 0. The rest follow the order.
+    pub levels: HashMap<usize, String>,
+}
+
+impl Default for ThemesConfig {
+    fn default() -> Self {
+        ThemesConfig {
+            order: Vec::new(),
+            waves_per_theme: 1,
+            levels: HashMap::default(),
+        }
+    }
+}
+
+// Everything a theme changes, read from its manifest.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ThemeManifest {
+    // Texture tiled over the whole arena.
+    pub background: Option<String>,
+    pub tile_size: (f32, f32),
+    pub background_color: (f32, f32, f32, f32),
+    pub clear_color: (f32, f32, f32),
+    // Sprites swapped for their variants, from the usual path to the variant's path.
+    pub sprites: HashMap<String, String>,
+    // Sounds played with other variations.
+    pub sounds: HashMap<Sound, Vec<String>>,
+}
+
+impl Default for ThemeManifest {
+    fn default() -> Self {
+        ThemeManifest {
+            background: None,
+            tile_size: (64.0, 64.0),
+            background_color: (1.0, 1.0, 1.0, 1.0),
+            clear_color: (0.4, 0.4, 0.4),
+            sprites: HashMap::default(),
+            sounds: HashMap::default(),
+        }
+    }
+}
+
+// Theme with its assets loaded up front, so switching doesn't load anything mid-game.
+pub struct Theme {
+    pub name: String,
+    pub manifest: ThemeManifest,
+    pub background: Option<Handle<Image>>,
+    pub sprites: HashMap<Handle<Image>, Handle<Image>>,
+    pub sounds: HashMap<Sound, Vec<Handle<AudioSource>>>,
+}
+
+#[derive(Resource, Default)]
+pub struct ThemeLibrary {
+    pub config: ThemesConfig,
+    pub themes: Vec<Theme>,
+}
+
+impl ThemeLibrary {
+    // Index of the theme for the given puzzle level.
+    pub fn for_level(&self, level: usize) -> Option<usize> {
+        if self.themes.is_empty() {
+            return None;
+        }
+
+        let pinned = self
+            .config
+            .levels
+            .get(&level)
+            .and_then(|name| self.themes.iter().position(|theme| &theme.name == name));
+        Some(pinned.unwrap_or(level % self.themes.len()))
+    }
+
+    // Index of the theme for the given wave.
+    pub fn for_wave(&self, wave: usize) -> Option<usize> {
+        if self.themes.is_empty() {
+            return None;
+        }
+
+        Some(wave / self.config.waves_per_theme.max(1) % self.themes.len())
+    }
+}
+
+// Index of the theme the arena is in, none outside of the game.
+#[derive(Resource, Default)]
+pub struct ActiveTheme {
+    pub index: Option<usize>,
+}
@@ -0,0 +1,233 @@
+use super::components::*;
+use super::resources::*;
+use super::{BACKGROUND_Z, THEMES_CONFIG_PATH, THEMES_DIR};
+use crate::game::audio::resources::SoundLibrary;
+use crate::game::enemy::resources::Wave;
+use crate::game::objective::resources::Level;
+use crate::game::{GameMode, ARENA_HEIGHT, ARENA_WIDTH};
+
+use bevy::prelude::*;
+use std::path::Path;
+
+// Reads the theme rotation and the manifests of its themes. Without them the arena
+// keeps its default looks.
+pub fn load_themes(mut theme_library: ResMut<ThemeLibrary>, asset_server: Res<AssetServer>) {
+    let config = match std::fs::read_to_string(THEMES_CONFIG_PATH) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not read {}: {}", THEMES_CONFIG_PATH, error);
+            return;
+        }
+    };
+    let config = match ron::from_str::<ThemesConfig>(&config) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("Could not parse {}: {}", THEMES_CONFIG_PATH, error);
+            return;
+        }
+    };
+
+    for name in config.order.iter() {
+        let path = Path::new(THEMES_DIR).join(format!("{}.ron", name));
+        let manifest = match std::fs::read_to_string(&path) {
+            Ok(v) => v,
+            Err(error) => {
+                println!("Could not read {}: {}", path.display(), error);
+                continue;
+            }
+        };
+        let manifest = match ron::from_str::<ThemeManifest>(&manifest) {
+            Ok(v) => v,
+            Err(error) => {
+                println!("Could not parse {}: {}", path.display(), error);
+                continue;
+            }
+        };
+
+        theme_library.themes.push(Theme {
+            name: name.clone(),
+            background: manifest
+                .background
+                .as_ref()
+                .map(|path| asset_server.load(path.as_str())),
+            sprites: manifest
+                .sprites
+                .iter()
+                .map(|(original, variant)| {
+                    (
+                        asset_server.load(original.as_str()),
+                        asset_server.load(variant.as_str()),
+                    )
+                })
+                .collect(),
+            sounds: manifest
+                .sounds
+                .iter()
+                .map(|(sound, paths)| {
+                    let handles = paths
+                        .iter()
+                        .map(|path| asset_server.load(path.as_str()))
+                        .collect();
+                    (*sound, handles)
+                })
+                .collect(),
+            manifest,
+        });
+    }
+    theme_library.config = config;
+}
+
+// Puzzle levels and waves each get their own theme.
+pub fn select_theme(
+    mut active_theme: ResMut<ActiveTheme>,
+    theme_library: Res<ThemeLibrary>,
+    mut sound_library: ResMut<SoundLibrary>,
+    mut clear_color: ResMut<ClearColor>,
+    game_mode: Res<GameMode>,
+    level: Res<Level>,
+    wave: Res<Wave>,
+) {
+    let wanted = match *game_mode {
+        GameMode::Puzzle => theme_library.for_level(level.index),
+        _ => theme_library.for_wave(wave.number),
+    };
+    if active_theme.index == wanted {
+        return;
+    }
+    active_theme.index = wanted;
+
+    let theme = match wanted.and_then(|index| theme_library.themes.get(index)) {
+        Some(v) => v,
+        None => return,
+    };
+
+    let (r, g, b) = theme.manifest.clear_color;
+    clear_color.0 = Color::rgb(r, g, b);
+    sound_library.themed = theme.sounds.clone();
+    println!("Arena theme: {}", theme.name);
+}
+
+// Tiles the theme's background over the whole arena, again whenever the theme changes.
+pub fn spawn_background(
+    mut commands: Commands,
+    tile_query: Query<Entity, With<BackgroundTile>>,
+    active_theme: Res<ActiveTheme>,
+    theme_library: Res<ThemeLibrary>,
+) {
+    if !active_theme.is_changed() {
+        return;
+    }
+
+    for tile_entity in tile_query.iter() {
+        commands.entity(tile_entity).despawn();
+    }
+
+    let theme = match active_theme
+        .index
+        .and_then(|index| theme_library.themes.get(index))
+    {
+        Some(v) => v,
+        None => return,
+    };
+    let background = match &theme.background {
+        Some(v) => v,
+        None => return,
+    };
+
+    let tile_size = Vec2::from(theme.manifest.tile_size).max(Vec2::ONE);
+    let columns = (ARENA_WIDTH / tile_size.x).ceil() as usize;
+    let rows = (ARENA_HEIGHT / tile_size.y).ceil() as usize;
+    let (r, g, b, a) = theme.manifest.background_color;
+
+    for column in 0..columns {
+        for row in 0..rows {
+            let position = (Vec2::new(column as f32, row as f32) + 0.5) * tile_size;
+
+            commands.spawn((
+                SpriteBundle {
+                    transform: Transform::from_translation(position.extend(BACKGROUND_Z)),
+                    texture: background.clone(),
+                    sprite: Sprite {
+                        color: Color::rgba(r, g, b, a),
+                        custom_size: Some(tile_size),
+                        ..default()
+                    },
+                    ..default()
+                },
+                BackgroundTile {},
+            ));
+        }
+    }
+}
+
+// Swaps the sprites for the theme's variants. Sprites that just spawned or got a new texture
+// are swapped right away, all of them are once the theme changes.
+#[allow(clippy::type_complexity)]
+pub fn apply_theme_sprites(
+    mut commands: Commands,
+    mut sprite_query: Query<(Entity, &mut Handle<Image>, Option<&mut ThemedSprite>), With<Sprite>>,
+    active_theme: Res<ActiveTheme>,
+    theme_library: Res<ThemeLibrary>,
+) {
+    let theme = active_theme
+        .index
+        .and_then(|index| theme_library.themes.get(index));
+
+    for (entity, mut texture, themed_sprite) in sprite_query.iter_mut() {
+        if !active_theme.is_changed() && !texture.is_changed() {
+            continue;
+        }
+
+        // Unless the game swapped the texture on its own, like when the exit unlocks,
+        // the sprite still stands for its original texture.
+        let original = match &themed_sprite {
+            Some(themed_sprite) if *texture == themed_sprite.texture => {
+                themed_sprite.original.clone()
+            }
+            _ => texture.clone(),
+        };
+        let variant = theme
+            .and_then(|theme| theme.sprites.get(&original))
+            .unwrap_or(&original)
+            .clone();
+
+        if *texture != variant {
+            *texture = variant.clone();
+        }
+
+        match themed_sprite {
+            Some(mut themed_sprite) => {
+                themed_sprite.original = original;
+                themed_sprite.texture = variant;
+            }
+            None if variant != original => {
+                commands.entity(entity).insert(ThemedSprite {
+                    original,
+                    texture: variant,
+                });
+            }
+            None => {}
+        }
+    }
+}
+
+pub fn clear_theme(
+    mut commands: Commands,
+    tile_query: Query<Entity, With<BackgroundTile>>,
+    mut sprite_query: Query<(Entity, &mut Handle<Image>, &ThemedSprite)>,
+    mut active_theme: ResMut<ActiveTheme>,
+    mut sound_library: ResMut<SoundLibrary>,
+    mut clear_color: ResMut<ClearColor>,
+) {
+    for tile_entity in tile_query.iter() {
+        commands.entity(tile_entity).despawn();
+    }
+    for (entity, mut texture, themed_sprite) in sprite_query.iter_mut() {
+        *texture = themed_sprite.original.clone();
+        commands.entity(entity).remove::<ThemedSprite>();
+    }
+
+    active_theme.index = None;
+    sound_library.themed.clear();
+    *clear_color = ClearColor::default();
+}
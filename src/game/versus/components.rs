@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+// Push received from bumping into another player, fades out over time.
+#[derive(Component)]
+pub struct Knockback {
+    pub velocity: Vec3,
+}
@@ -0,0 +1,43 @@
+pub mod components;
+pub mod resources;
+mod systems;
+
+use super::in_versus_mode;
+use super::simulation::SimulationSchedule;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const VERSUS_TIME_LIMIT: f32 = 90.0;
+pub const VERSUS_SCORE_TARGET: usize = 25;
+pub const VERSUS_HIT_PENALTY: usize = 3; // Points an enemy steals from the player it hits.
+pub const BUMP_IMPULSE: f32 = 700.0;
+pub const KNOCKBACK_DAMPING: f32 = 6.0; // Fraction of the knockback lost per second.
+
+pub struct VersusPlugin;
+
+impl Plugin for VersusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VersusMatch>()
+            .init_resource::<MatchResult>()
+            // Upon entering the game state, start a new match
+            .add_system(
+                reset_match
+                    .run_if(in_versus_mode)
+                    .in_schedule(OnEnter(AppState::Game)),
+            )
+            .add_systems(
+                (
+                    bump_players,
+                    apply_knockback.after(bump_players),
+                    steal_points_on_hit,
+                    match_timer_tick,
+                    check_match_end.after(match_timer_tick),
+                )
+                    .distributive_run_if(in_versus_mode)
+                    .in_schedule(SimulationSchedule),
+            );
+    }
+}
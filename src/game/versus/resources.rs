@@ -0,0 +1,26 @@
+use super::VERSUS_TIME_LIMIT;
+use crate::events::PlayerResult;
+use crate::game::player::components::PlayerId;
+
+use bevy::prelude::*;
+
+#[derive(Resource)]
+pub struct VersusMatch {
+    pub timer: Timer,
+}
+
+impl Default for VersusMatch {
+    fn default() -> Self {
+        VersusMatch {
+            timer: Timer::from_seconds(VERSUS_TIME_LIMIT, TimerMode::Once),
+        }
+    }
+}
+
+// Outcome of the last versus match, shown on the winner screen.
+#[derive(Resource, Default)]
+pub struct MatchResult {
+    // None when the match ended in a tie.
+    pub winner: Option<PlayerId>,
+    pub players: Vec<PlayerResult>,
+}
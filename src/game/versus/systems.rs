@@ -0,0 +1,161 @@
+use super::components::*;
+use super::resources::*;
+use super::{BUMP_IMPULSE, KNOCKBACK_DAMPING, VERSUS_HIT_PENALTY, VERSUS_SCORE_TARGET};
+use crate::events::{GameOver, PlayerHit, Sound, SoundEvent};
+use crate::game::player::components::{Invulnerable, Player, PlayerId};
+use crate::game::player::resources::PlayerCount;
+use crate::game::player::{INVULNERABILITY_DURATION, PLAYER_SIZE};
+use crate::game::score::resources::Score;
+use crate::game::simulation::resources::GameRng;
+
+use bevy::prelude::*;
+
+pub fn reset_match(mut versus_match: ResMut<VersusMatch>, mut match_result: ResMut<MatchResult>) {
+    *versus_match = VersusMatch::default();
+    *match_result = MatchResult::default();
+}
+
+// Pushes overlapping players apart and knocks them back from each other.
+pub fn bump_players(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut Transform), With<Player>>,
+    knockback_query: Query<&Knockback>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+) {
+    let mut players = player_query.iter_combinations_mut();
+
+    while let Some([(entity_a, mut transform_a), (entity_b, mut transform_b)]) =
+        players.fetch_next()
+    {
+        let offset = (transform_b.translation - transform_a.translation).truncate();
+        let distance = offset.length();
+        if distance >= PLAYER_SIZE {
+            continue;
+        }
+
+        let contact = (transform_a.translation + transform_b.translation) / 2.0;
+        let normal = if distance > 0.0 {
+            (offset / distance).extend(0.0)
+        } else {
+            Vec3::X
+        };
+
+        // Separate the players so they no longer overlap.
+        let overlap = PLAYER_SIZE - distance;
+        transform_a.translation -= normal * overlap / 2.0;
+        transform_b.translation += normal * overlap / 2.0;
+
+        // Don't stack the knockback while the players are still flying apart.
+        if knockback_query.contains(entity_a) || knockback_query.contains(entity_b) {
+            continue;
+        }
+
+        commands.entity(entity_a).insert(Knockback {
+            velocity: -normal * BUMP_IMPULSE,
+        });
+        commands.entity(entity_b).insert(Knockback {
+            velocity: normal * BUMP_IMPULSE,
+        });
+
+        sound_event_writer.send(SoundEvent::at(Sound::PlayersBumped, contact));
+    }
+}
+
+pub fn apply_knockback(
+    mut commands: Commands,
+    mut knockback_query: Query<(Entity, &mut Transform, &mut Knockback)>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut knockback) in knockback_query.iter_mut() {
+        transform.translation += knockback.velocity * time.delta_seconds();
+        knockback.velocity *= (1.0 - KNOCKBACK_DAMPING * time.delta_seconds()).max(0.0);
+
+        if knockback.velocity.length() < 10.0 {
+            commands.entity(entity).remove::<Knockback>();
+        }
+    }
+}
+
+// In versus mode enemies steal points from the players they hit instead of taking lives.
+pub fn steal_points_on_hit(
+    mut commands: Commands,
+    mut player_hit_event_reader: EventReader<PlayerHit>,
+    player_query: Query<(&Player, &Transform), Without<Invulnerable>>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    mut score: ResMut<Score>,
+) {
+    let mut hit_players: Vec<Entity> = Vec::new();
+
+    for event in player_hit_event_reader.iter() {
+        if hit_players.contains(&event.player_entity) {
+            continue;
+        }
+        let (player, player_transform) = match player_query.get(event.player_entity) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        hit_players.push(event.player_entity);
+
+        let stolen_points = score.deduct(player.id, VERSUS_HIT_PENALTY);
+        println!("Player {} lost {} points!", player.id.0 + 1, stolen_points);
+
+        sound_event_writer.send(SoundEvent::at(
+            Sound::VersusHit,
+            player_transform.translation,
+        ));
+
+        commands.entity(event.player_entity).insert(Invulnerable {
+            timer: Timer::from_seconds(INVULNERABILITY_DURATION, TimerMode::Once),
+        });
+    }
+}
+
+pub fn match_timer_tick(mut versus_match: ResMut<VersusMatch>, time: Res<Time>) {
+    versus_match.timer.tick(time.delta());
+}
+
+// Ends the match once the time is up or someone reaches the score target.
+pub fn check_match_end(
+    mut game_over_event_writer: EventWriter<GameOver>,
+    versus_match: Res<VersusMatch>,
+    score: Res<Score>,
+    player_count: Res<PlayerCount>,
+    mut match_result: ResMut<MatchResult>,
+    game_rng: Res<GameRng>,
+) {
+    // The match is already over, waiting for the state to change.
+    if !match_result.players.is_empty() {
+        return;
+    }
+
+    let players = score.player_results(player_count.value);
+    let best_score = players.iter().map(|player| player.score).max().unwrap_or(0);
+
+    if !versus_match.timer.just_finished() && best_score < VERSUS_SCORE_TARGET {
+        return;
+    }
+
+    let leaders: Vec<PlayerId> = players
+        .iter()
+        .filter(|player| player.score == best_score)
+        .map(|player| player.id)
+        .collect();
+
+    match_result.winner = if leaders.len() == 1 {
+        Some(leaders[0])
+    } else {
+        None
+    };
+    match_result.players = players.clone();
+
+    match match_result.winner {
+        Some(winner) => println!("Player {} wins the match!", winner.0 + 1),
+        None => println!("The match ended in a tie!"),
+    }
+
+    game_over_event_writer.send(GameOver {
+        score_value: score.value,
+        players,
+        seed: game_rng.seed,
+    });
+}
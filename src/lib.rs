@@ -0,0 +1,17 @@
+pub mod events;
+pub mod game;
+pub mod network;
+pub mod settings;
+pub mod systems;
+pub mod ui;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default, Deserialize)]
+pub enum AppState {
+    #[default]
+    Menu,
+    Game,
+    GameOver,
+}
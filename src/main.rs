@@ -1,18 +1,73 @@
-mod events;
-mod game;
-mod systems;
-mod ui;
-
-use game::GamePlugin;
-use systems::*;
-use ui::UIPlugin;
+use bevy_first_game::game::player::components::PlayerId;
+use bevy_first_game::game::GamePlugin;
+use bevy_first_game::network::client::ClientPlugin;
+use bevy_first_game::network::rollback::resources::SessionKind;
+use bevy_first_game::network::rollback::rollback_app;
+use bevy_first_game::network::server::server_app;
+use bevy_first_game::network::transport::{NetTransport, UdpTransport};
+use bevy_first_game::network::LaunchMode;
+use bevy_first_game::settings::resources::Settings;
+use bevy_first_game::settings::SettingsPlugin;
+use bevy_first_game::systems::*;
+use bevy_first_game::ui::UIPlugin;
+use bevy_first_game::AppState;
 
 use bevy::prelude::*;
+use rand::prelude::*;
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    match LaunchMode::from_args(&args) {
+        LaunchMode::Local => run_local_game(),
+        LaunchMode::Server(address) => {
+            let transport = UdpTransport::bind(address).expect("Failed to bind the server socket");
+            println!("Server listening on {}", address);
+            server_app(NetTransport::new(transport)).run();
+        }
+        LaunchMode::Client(server_address) => {
+            let transport = UdpTransport::bind("0.0.0.0:0".parse().unwrap())
+                .expect("Failed to bind the client socket");
+            App::new()
+                .add_plugins(DefaultPlugins)
+                .add_plugin(ClientPlugin { server_address })
+                .insert_resource(NetTransport::new(transport))
+                .add_startup_system(spawn_camera)
+                .add_system(close_game)
+                .run();
+        }
+        LaunchMode::Rollback { local, peer } => {
+            let transport = UdpTransport::bind(local).expect("Failed to bind the peer socket");
+            // Both peers agree on who's who and on the seed without talking to each other.
+            let local_player = if local < peer {
+                PlayerId(0)
+            } else {
+                PlayerId(1)
+            };
+            let seed =
+                local.port().min(peer.port()) as u64 * 65536 + local.port().max(peer.port()) as u64;
+            let kind = SessionKind::Peer {
+                address: peer,
+                local_player,
+            };
+            rollback_app(kind, seed, Some(NetTransport::new(transport))).run();
+        }
+        LaunchMode::SyncTest => rollback_app(SessionKind::SyncTest, random(), None).run(),
+    }
+}
+
+fn run_local_game() {
+    // The window is created with the saved settings, instead of being resized right after.
+    let settings = Settings::load();
+
     App::new()
-        .add_plugins(DefaultPlugins)
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(settings.window()),
+            ..default()
+        }))
+        .insert_resource(settings)
         .add_state::<AppState>()
+        .add_plugin(SettingsPlugin)
         .add_plugin(GamePlugin)
         .add_plugin(UIPlugin)
         .add_startup_system(spawn_camera)
@@ -22,11 +77,3 @@ fn main() {
         .add_system(transition_to_menu_state)
         .run();
 }
-
-#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
-pub enum AppState {
-    #[default]
-    Menu,
-    Game,
-    GameOver,
-}
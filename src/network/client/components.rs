@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+// Local copy of an entity simulated on the server.
+#[derive(Component)]
+pub struct Replicated {
+    pub net_id: u64,
+}
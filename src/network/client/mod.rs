@@ -0,0 +1,31 @@
+mod components;
+pub mod resources;
+mod systems;
+
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+use std::net::SocketAddr;
+
+// Sends the local input to the server and draws the world from its snapshots.
+// Nothing is simulated on the client.
+pub struct ClientPlugin {
+    pub server_address: SocketAddr,
+}
+
+impl Plugin for ClientPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ServerConnection::new(self.server_address))
+            .init_resource::<SnapshotBuffer>()
+            .add_systems((
+                receive_server_messages,
+                connect_to_server.after(receive_server_messages),
+                send_input.after(receive_server_messages),
+                interpolate_snapshots.after(receive_server_messages),
+                check_server_timeout.after(receive_server_messages),
+            ))
+            // Runs after every other system had the chance to request the exit.
+            .add_system(disconnect_on_exit.in_base_set(CoreSet::Last));
+    }
+}
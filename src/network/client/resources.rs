@@ -0,0 +1,39 @@
+use crate::game::player::components::PlayerId;
+use crate::network::messages::Snapshot;
+use crate::network::CONNECT_RETRY_PERIOD;
+
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+
+#[derive(Resource)]
+pub struct ServerConnection {
+    pub address: SocketAddr,
+    // Assigned by the server once it accepts the connection.
+    pub player_id: Option<PlayerId>,
+    pub last_heard: f32,
+    pub connect_timer: Timer,
+    pub score: usize,
+}
+
+impl ServerConnection {
+    pub fn new(address: SocketAddr) -> Self {
+        let mut connect_timer = Timer::from_seconds(CONNECT_RETRY_PERIOD, TimerMode::Repeating);
+        // Say hello on the very first frame.
+        connect_timer.set_elapsed(connect_timer.duration());
+
+        ServerConnection {
+            address,
+            player_id: None,
+            last_heard: 0.0,
+            connect_timer,
+            score: 0,
+        }
+    }
+}
+
+// Latest snapshots together with the time they arrived, oldest first.
+#[derive(Resource, Default)]
+pub struct SnapshotBuffer {
+    pub snapshots: VecDeque<(f32, Snapshot)>,
+}
@@ -0,0 +1,242 @@
+use super::components::*;
+use super::resources::*;
+use crate::game::player::components::PlayerId;
+use crate::game::player::keyboard_intent;
+use crate::network::messages::*;
+use crate::network::transport::NetTransport;
+use crate::network::{CONNECTION_TIMEOUT, INTERPOLATION_DELAY, SNAPSHOT_BUFFER_SIZE};
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+// Keeps asking the server to let us in until it answers.
+pub fn connect_to_server(
+    mut net_transport: ResMut<NetTransport>,
+    mut server_connection: ResMut<ServerConnection>,
+    time: Res<Time>,
+) {
+    if server_connection.player_id.is_some() {
+        return;
+    }
+
+    server_connection.connect_timer.tick(time.delta());
+    if !server_connection.connect_timer.just_finished() {
+        return;
+    }
+
+    let address = server_connection.address;
+    net_transport.send_message(address, &ClientMessage::Connect);
+}
+
+pub fn receive_server_messages(
+    mut net_transport: ResMut<NetTransport>,
+    mut server_connection: ResMut<ServerConnection>,
+    mut snapshot_buffer: ResMut<SnapshotBuffer>,
+    mut app_exit_event_writer: EventWriter<AppExit>,
+    time: Res<Time>,
+) {
+    while let Some((address, message)) = net_transport.receive_message::<ServerMessage>() {
+        // Ignore anybody but our server.
+        if address != server_connection.address {
+            continue;
+        }
+        server_connection.last_heard = time.elapsed_seconds();
+
+        match message {
+            ServerMessage::Welcome { player_id } => {
+                if server_connection.player_id.is_none() {
+                    println!("Connected to {} as player {}", address, player_id + 1);
+                }
+                server_connection.player_id = Some(PlayerId(player_id));
+            }
+            ServerMessage::Full => {
+                println!("The server at {} is full!", address);
+                app_exit_event_writer.send(AppExit);
+            }
+            ServerMessage::Snapshot(snapshot) => {
+                // Snapshots may arrive out of order, the older ones are useless.
+                let is_newer = match snapshot_buffer.snapshots.back() {
+                    Some((_, latest)) => snapshot.tick > latest.tick,
+                    None => true,
+                };
+                if !is_newer {
+                    continue;
+                }
+
+                if let Some(player_id) = server_connection.player_id {
+                    let score = snapshot.scores.get(player_id.0).copied().unwrap_or(0);
+                    if score != server_connection.score {
+                        server_connection.score = score;
+                        println!("Score: {}", score);
+                    }
+                }
+
+                snapshot_buffer
+                    .snapshots
+                    .push_back((time.elapsed_seconds(), snapshot));
+                if snapshot_buffer.snapshots.len() > SNAPSHOT_BUFFER_SIZE {
+                    snapshot_buffer.snapshots.pop_front();
+                }
+            }
+        }
+    }
+}
+
+// Sends the local player's input to the server every frame.
+pub fn send_input(
+    mut net_transport: ResMut<NetTransport>,
+    server_connection: Res<ServerConnection>,
+    keyboard_input: Res<Input<KeyCode>>,
+) {
+    if server_connection.player_id.is_none() {
+        return;
+    }
+
+    let intent = keyboard_intent(
+        &keyboard_input,
+        [KeyCode::W, KeyCode::S, KeyCode::A, KeyCode::D],
+        KeyCode::LShift,
+    );
+
+    net_transport.send_message(
+        server_connection.address,
+        &ClientMessage::Input {
+            direction: intent.direction.truncate().into(),
+            dash: intent.dash,
+        },
+    );
+}
+
+// Draws the world slightly in the past, in between the two snapshots around that moment.
+pub fn interpolate_snapshots(
+    mut commands: Commands,
+    mut replicated_query: Query<(Entity, &Replicated, &mut Transform)>,
+    snapshot_buffer: Res<SnapshotBuffer>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+) {
+    let snapshots = &snapshot_buffer.snapshots;
+    if snapshots.is_empty() {
+        return;
+    }
+
+    let render_time = time.elapsed_seconds() - INTERPOLATION_DELAY;
+    let (from, to, alpha) = match snapshots.iter().position(|(time, _)| *time >= render_time) {
+        Some(index) if index > 0 => {
+            let (from_time, from) = &snapshots[index - 1];
+            let (to_time, to) = &snapshots[index];
+            (from, to, (render_time - from_time) / (to_time - from_time))
+        }
+        // Not enough history yet, show the oldest snapshot.
+        Some(_) => (&snapshots[0].1, &snapshots[0].1, 1.0),
+        // The server went quiet, hold the latest snapshot.
+        None => {
+            let latest = &snapshots[snapshots.len() - 1].1;
+            (latest, latest, 1.0)
+        }
+    };
+
+    let from_positions: HashMap<u64, Vec2> = from
+        .entities
+        .iter()
+        .map(|entity| (entity.net_id, Vec2::from(entity.position)))
+        .collect();
+    let mut local_entities: HashMap<u64, Entity> = HashMap::new();
+
+    for (entity, replicated, _) in replicated_query.iter() {
+        local_entities.insert(replicated.net_id, entity);
+    }
+
+    for net_entity in to.entities.iter() {
+        let to_position = Vec2::from(net_entity.position);
+        let position = match from_positions.get(&net_entity.net_id) {
+            Some(from_position) => from_position.lerp(to_position, alpha),
+            None => to_position,
+        };
+
+        match local_entities.remove(&net_entity.net_id) {
+            Some(entity) => {
+                if let Ok((_, _, mut transform)) = replicated_query.get_mut(entity) {
+                    transform.translation = position.extend(transform.translation.z);
+                }
+            }
+            None => {
+                commands.spawn((
+                    replicated_sprite(net_entity.kind, position, &asset_server),
+                    Replicated {
+                        net_id: net_entity.net_id,
+                    },
+                ));
+            }
+        }
+    }
+
+    // Whatever is left is gone on the server.
+    for entity in local_entities.into_values() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn replicated_sprite(
+    kind: NetEntityKind,
+    position: Vec2,
+    asset_server: &AssetServer,
+) -> SpriteBundle {
+    let (texture, color, scale) = match kind {
+        NetEntityKind::Player(id) => (
+            "sprites/Default/ball_blue_large.png",
+            PlayerId(id).color(),
+            1.0,
+        ),
+        NetEntityKind::Enemy => ("sprites/Default/ball_red_large.png", Color::WHITE, 1.0),
+        NetEntityKind::Star => ("sprites/Default/star.png", Color::WHITE, 1.0),
+        NetEntityKind::Boss => ("sprites/Default/ball_red_large_alt.png", Color::WHITE, 2.0),
+    };
+
+    SpriteBundle {
+        transform: Transform::from_translation(position.extend(0.0)).with_scale(Vec3::splat(scale)),
+        texture: asset_server.load(texture),
+        sprite: Sprite { color, ..default() },
+        ..default()
+    }
+}
+
+// Starts over with a fresh connection when the server stops answering.
+pub fn check_server_timeout(
+    mut commands: Commands,
+    replicated_query: Query<Entity, With<Replicated>>,
+    mut server_connection: ResMut<ServerConnection>,
+    mut snapshot_buffer: ResMut<SnapshotBuffer>,
+    time: Res<Time>,
+) {
+    if server_connection.player_id.is_none()
+        || time.elapsed_seconds() - server_connection.last_heard < CONNECTION_TIMEOUT
+    {
+        return;
+    }
+
+    println!(
+        "Lost connection to {}, reconnecting...",
+        server_connection.address
+    );
+    *server_connection = ServerConnection::new(server_connection.address);
+    snapshot_buffer.snapshots.clear();
+
+    for entity in replicated_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// Lets the server know right away instead of waiting for the timeout.
+pub fn disconnect_on_exit(
+    mut app_exit_event_reader: EventReader<AppExit>,
+    mut net_transport: ResMut<NetTransport>,
+    server_connection: Res<ServerConnection>,
+) {
+    if app_exit_event_reader.iter().next().is_none() || server_connection.player_id.is_none() {
+        return;
+    }
+
+    net_transport.send_message(server_connection.address, &ClientMessage::Disconnect);
+}
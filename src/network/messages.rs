@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ClientMessage {
+    // Sent repeatedly until the server answers with a welcome.
+    Connect,
+    Input { direction: [f32; 2], dash: bool },
+    Disconnect,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ServerMessage {
+    Welcome { player_id: usize },
+    // Every player slot is taken.
+    Full,
+    Snapshot(Snapshot),
+}
+
+// State of the world the clients need to draw it.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Snapshot {
+    pub tick: u64,
+    pub entities: Vec<NetEntity>,
+    pub scores: Vec<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct NetEntity {
+    // Server side entity bits, stable for as long as the entity lives.
+    pub net_id: u64,
+    pub kind: NetEntityKind,
+    pub position: [f32; 2],
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum NetEntityKind {
+    Player(usize),
+    Enemy,
+    Star,
+    Boss,
+}
+
+// Exchanged between the two peers of a rollback session.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum PeerMessage {
+    // Sent until the other peer shows up.
+    Hello,
+    // Every input the peer hasn't confirmed yet, starting at the given tick.
+    Inputs {
+        first_tick: u64,
+        inputs: Vec<NetInput>,
+        // All of the other peer's inputs before this tick arrived.
+        received_until: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct NetInput {
+    pub direction: [f32; 2],
+    pub dash: bool,
+}
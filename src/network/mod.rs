@@ -0,0 +1,68 @@
+pub mod client;
+pub mod messages;
+pub mod rollback;
+pub mod server;
+pub mod transport;
+
+use bevy::app::PluginGroupBuilder;
+use bevy::prelude::*;
+use bevy::render::settings::WgpuSettings;
+use bevy::render::RenderPlugin;
+use bevy::winit::WinitPlugin;
+use std::net::SocketAddr;
+
+pub const SNAPSHOT_PERIOD: f32 = 0.05; // How often the server sends the world state to the clients.
+pub const CONNECT_RETRY_PERIOD: f32 = 0.5;
+pub const CONNECTION_TIMEOUT: f32 = 5.0; // Seconds of silence before the other side is considered gone.
+pub const INTERPOLATION_DELAY: f32 = 0.1; // Clients render this far in the past to smooth out the snapshots.
+pub const SNAPSHOT_BUFFER_SIZE: usize = 32;
+pub const MAX_PACKET_SIZE: usize = 65507;
+
+// How the game was launched, picked from the command line arguments.
+pub enum LaunchMode {
+    Local,
+    Server(SocketAddr),
+    Client(SocketAddr),
+    Rollback { local: SocketAddr, peer: SocketAddr },
+    SyncTest,
+}
+
+impl LaunchMode {
+    // `--server <address>` hosts a headless game and `--connect <address>` joins one.
+    // `--rollback <local address> <peer address>` plays peer-to-peer and `--sync-test`
+    // checks the simulation for desyncs locally.
+    pub fn from_args(args: &[String]) -> LaunchMode {
+        let address = |index: usize| -> SocketAddr {
+            args.get(index)
+                .and_then(|address| address.parse().ok())
+                .expect("Expected an address like 127.0.0.1:7777")
+        };
+
+        match args.iter().position(|arg| arg.starts_with("--")) {
+            Some(index) => match args[index].as_str() {
+                "--server" => LaunchMode::Server(address(index + 1)),
+                "--connect" => LaunchMode::Client(address(index + 1)),
+                "--rollback" => LaunchMode::Rollback {
+                    local: address(index + 1),
+                    peer: address(index + 2),
+                },
+                "--sync-test" => LaunchMode::SyncTest,
+                _ => LaunchMode::Local,
+            },
+            None => LaunchMode::Local,
+        }
+    }
+}
+
+// Default plugins without a window nor a renderer, for running the game without a screen.
+pub fn headless_plugins() -> PluginGroupBuilder {
+    DefaultPlugins
+        .build()
+        .disable::<WinitPlugin>()
+        .set(RenderPlugin {
+            wgpu_settings: WgpuSettings {
+                backends: None,
+                ..default()
+            },
+        })
+}
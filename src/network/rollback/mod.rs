@@ -0,0 +1,65 @@
+pub mod resources;
+mod snapshot;
+mod systems;
+
+use super::transport::NetTransport;
+use crate::game::player::resources::PlayerCount;
+use crate::game::simulation::resources::{GameRng, SimulationClock};
+use crate::game::{GamePlugin, GameState};
+use crate::systems::{close_game, handle_game_over_event, spawn_camera};
+use crate::ui::UIPlugin;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const MAX_PREDICTION_TICKS: u64 = 8; // How far a peer may run ahead of the inputs it got.
+pub const SYNC_TEST_DEPTH: u64 = 2; // Ticks rolled back and simulated again every tick in the sync test.
+
+// Runs a deterministic session, either against a peer or as a local sync test.
+pub struct RollbackPlugin {
+    pub kind: SessionKind,
+}
+
+impl Plugin for RollbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(RollbackSession::new(self.kind))
+            // The session steps the simulation on its own.
+            .insert_resource(SimulationClock {
+                manual: true,
+                ..default()
+            })
+            .add_startup_system(start_session_game)
+            .add_system(spawn_session_players.in_schedule(OnEnter(AppState::Game)))
+            .add_system(end_session.in_schedule(OnEnter(AppState::GameOver)))
+            .add_system(
+                advance_session
+                    .in_set(OnUpdate(AppState::Game))
+                    .in_set(OnUpdate(GameState::Running)),
+            );
+    }
+}
+
+// Both peers have to start from the same seed, so it's derived from what they both know.
+pub fn rollback_app(kind: SessionKind, seed: u64, net_transport: Option<NetTransport>) -> App {
+    let mut app = App::new();
+
+    app.add_plugins(DefaultPlugins)
+        .add_state::<AppState>()
+        .add_plugin(GamePlugin)
+        .add_plugin(UIPlugin)
+        .add_plugin(RollbackPlugin { kind })
+        .insert_resource(GameRng::new(seed))
+        // The session spawns its own players.
+        .insert_resource(PlayerCount { value: 0 })
+        .add_startup_system(spawn_camera)
+        .add_system(close_game)
+        .add_system(handle_game_over_event);
+
+    if let Some(net_transport) = net_transport {
+        app.insert_resource(net_transport);
+    }
+
+    app
+}
@@ -0,0 +1,117 @@
+use super::snapshot::WorldSnapshot;
+use crate::game::player::components::PlayerId;
+use crate::network::messages::NetInput;
+
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+
+#[derive(Clone, Copy)]
+pub enum SessionKind {
+    // Two players on two machines, each peer simulates the whole game.
+    Peer {
+        address: SocketAddr,
+        local_player: PlayerId,
+    },
+    // Single local player, every tick gets rolled back and simulated again to catch desyncs.
+    SyncTest,
+}
+
+#[derive(Resource)]
+pub struct RollbackSession {
+    pub kind: SessionKind,
+    pub started: bool,
+    // Next tick to be simulated.
+    pub tick: u64,
+    pub accumulator: f32,
+    // A dash pressed on a frame without any tick, kept for the next tick.
+    pub pending_dash: bool,
+    pub local_inputs: BTreeMap<u64, NetInput>,
+    pub remote_inputs: BTreeMap<u64, NetInput>,
+    // What was assumed for the remote player on ticks whose input hasn't arrived yet.
+    pub predicted_inputs: BTreeMap<u64, NetInput>,
+    // All remote inputs before this tick arrived.
+    pub remote_received_until: u64,
+    // The peer got all our inputs before this tick.
+    pub peer_received_until: u64,
+    // State before simulating the tick.
+    pub snapshots: BTreeMap<u64, WorldSnapshot>,
+    // State after simulating the tick, used by the sync test.
+    pub checksums: BTreeMap<u64, u64>,
+}
+
+impl RollbackSession {
+    pub fn new(kind: SessionKind) -> Self {
+        RollbackSession {
+            kind,
+            started: false,
+            tick: 0,
+            accumulator: 0.0,
+            pending_dash: false,
+            local_inputs: BTreeMap::new(),
+            remote_inputs: BTreeMap::new(),
+            predicted_inputs: BTreeMap::new(),
+            remote_received_until: 0,
+            peer_received_until: 0,
+            snapshots: BTreeMap::new(),
+            checksums: BTreeMap::new(),
+        }
+    }
+
+    pub fn local_player(&self) -> PlayerId {
+        match self.kind {
+            SessionKind::Peer { local_player, .. } => local_player,
+            SessionKind::SyncTest => PlayerId(0),
+        }
+    }
+
+    pub fn remote_player(&self) -> Option<PlayerId> {
+        match self.kind {
+            SessionKind::Peer { local_player, .. } => Some(PlayerId(1 - local_player.0)),
+            SessionKind::SyncTest => None,
+        }
+    }
+
+    pub fn players(&self) -> Vec<PlayerId> {
+        let mut players = vec![self.local_player()];
+        players.extend(self.remote_player());
+        players.sort_by_key(|player_id| player_id.0);
+        players
+    }
+
+    // Remote input of the tick, or a guess when it hasn't arrived yet.
+    // Players tend to keep doing what they did, so the last known input is repeated.
+    pub fn remote_input(&mut self, tick: u64) -> NetInput {
+        if let Some(input) = self.remote_inputs.get(&tick) {
+            return *input;
+        }
+
+        let predicted_input = NetInput {
+            dash: false,
+            ..self
+                .remote_inputs
+                .range(..tick)
+                .next_back()
+                .map(|(_, input)| *input)
+                .unwrap_or_default()
+        };
+        self.predicted_inputs.insert(tick, predicted_input);
+        predicted_input
+    }
+
+    // Forgets the history no rollback can reach anymore.
+    pub fn prune(&mut self, keep_from: u64) {
+        self.snapshots = self.snapshots.split_off(&keep_from);
+        self.checksums = self.checksums.split_off(&keep_from);
+        // The last known remote input is still needed for predicting the next ones.
+        self.remote_inputs = self.remote_inputs.split_off(&keep_from.saturating_sub(1));
+        self.predicted_inputs = self.predicted_inputs.split_off(&keep_from);
+
+        // Local inputs are resent until the peer confirms them.
+        let keep_local_from = match self.kind {
+            SessionKind::Peer { .. } => keep_from.min(self.peer_received_until),
+            SessionKind::SyncTest => keep_from,
+        };
+        self.local_inputs = self.local_inputs.split_off(&keep_local_from);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::game::boss::boss_bundle;
+use crate::game::boss::components::Boss;
+use crate::game::enemy::components::Enemy;
+use crate::game::enemy::enemy_bundle;
+use crate::game::enemy::resources::{EnemyTimer, Wave};
+use crate::game::player::components::*;
+use crate::game::player::player_bundle;
+use crate::game::score::resources::{Combo, Score};
+use crate::game::simulation::resources::{GameRng, SimulationClock};
+use crate::game::star::components::Star;
+use crate::game::star::resources::StarTimer;
+use crate::game::star::star_bundle;
+
+use bevy::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+// Everything the simulation needs to carry on from a given tick.
+#[derive(Clone)]
+pub struct WorldSnapshot {
+    clock_tick: u64,
+    rng: ChaCha8Rng,
+    score: Score,
+    combo: Combo,
+    enemy_timer: Timer,
+    star_timer: Timer,
+    wave: usize,
+    players: Vec<PlayerSnapshot>,
+    enemies: Vec<(Transform, Enemy)>,
+    stars: Vec<Transform>,
+    bosses: Vec<(Transform, Boss)>,
+}
+
+#[derive(Clone)]
+struct PlayerSnapshot {
+    id: PlayerId,
+    transform: Transform,
+    velocity: Velocity,
+    lives: Lives,
+    dash_cooldown: DashCooldown,
+    dash: Option<Dash>,
+    invulnerable: Option<Invulnerable>,
+}
+
+impl WorldSnapshot {
+    pub fn save(world: &mut World) -> Self {
+        let players = world
+            .query::<(
+                &Player,
+                &Transform,
+                &Velocity,
+                &Lives,
+                &DashCooldown,
+                Option<&Dash>,
+                Option<&Invulnerable>,
+            )>()
+            .iter(world)
+            .map(
+                |(player, transform, velocity, lives, dash_cooldown, dash, invulnerable)| {
+                    PlayerSnapshot {
+                        id: player.id,
+                        transform: *transform,
+                        velocity: velocity.clone(),
+                        lives: lives.clone(),
+                        dash_cooldown: dash_cooldown.clone(),
+                        dash: dash.cloned(),
+                        invulnerable: invulnerable.cloned(),
+                    }
+                },
+            )
+            .collect();
+        let enemies = world
+            .query::<(&Transform, &Enemy)>()
+            .iter(world)
+            .map(|(transform, enemy)| (*transform, enemy.clone()))
+            .collect();
+        let stars = world
+            .query_filtered::<&Transform, With<Star>>()
+            .iter(world)
+            .copied()
+            .collect();
+        let bosses = world
+            .query::<(&Transform, &Boss)>()
+            .iter(world)
+            .map(|(transform, boss)| (*transform, boss.clone()))
+            .collect();
+
+        WorldSnapshot {
+            clock_tick: world.resource::<SimulationClock>().tick,
+            rng: world.resource::<GameRng>().rng.clone(),
+            score: world.resource::<Score>().clone(),
+            combo: world.resource::<Combo>().clone(),
+            enemy_timer: world.resource::<EnemyTimer>().timer.clone(),
+            star_timer: world.resource::<StarTimer>().timer.clone(),
+            wave: world.resource::<Wave>().number,
+            players,
+            enemies,
+            stars,
+            bosses,
+        }
+    }
+
+    pub fn restore(&self, world: &mut World) {
+        world.resource_mut::<SimulationClock>().tick = self.clock_tick;
+        world.resource_mut::<GameRng>().rng = self.rng.clone();
+        *world.resource_mut::<Score>() = self.score.clone();
+        *world.resource_mut::<Combo>() = self.combo.clone();
+        world.resource_mut::<EnemyTimer>().timer = self.enemy_timer.clone();
+        world.resource_mut::<StarTimer>().timer = self.star_timer.clone();
+        // The boss shows up when the wave changes, so only touch it if it really differs.
+        if world.resource::<Wave>().number != self.wave {
+            world.resource_mut::<Wave>().number = self.wave;
+        }
+
+        let asset_server = world.resource::<AssetServer>().clone();
+
+        // Enemies, stars and the boss have no identity, they're simply spawned again.
+        let entities: Vec<Entity> = world
+            .query_filtered::<Entity, Or<(With<Enemy>, With<Star>, With<Boss>)>>()
+            .iter(world)
+            .collect();
+        for entity in entities {
+            world.despawn(entity);
+        }
+        for (transform, enemy) in self.enemies.iter() {
+            let (mut sprite_bundle, enemy) =
+                enemy_bundle(transform.translation, enemy.direction, &asset_server);
+            sprite_bundle.transform = *transform;
+            world.spawn((sprite_bundle, enemy));
+        }
+        for transform in self.stars.iter() {
+            world.spawn(star_bundle(transform.translation, &asset_server));
+        }
+        for (transform, boss) in self.bosses.iter() {
+            let (mut sprite_bundle, boss) = boss_bundle(*transform, boss.clone(), &asset_server);
+            sprite_bundle.transform = *transform;
+            world.spawn((sprite_bundle, boss));
+        }
+
+        // Players are updated in place, so whatever tracks them keeps working.
+        let mut player_entities: HashMap<PlayerId, Entity> = world
+            .query::<(Entity, &Player)>()
+            .iter(world)
+            .map(|(entity, player)| (player.id, entity))
+            .collect();
+
+        for player in self.players.iter() {
+            let entity = match player_entities.remove(&player.id) {
+                Some(v) => v,
+                // The player was knocked out in the future we're rolling back from.
+                None => world
+                    .spawn((
+                        player_bundle(player.id, player.transform.translation, &asset_server),
+                        RemoteInput {},
+                    ))
+                    .id(),
+            };
+
+            let mut player_entity = world.entity_mut(entity);
+            player_entity.insert((
+                player.transform,
+                player.velocity.clone(),
+                player.lives.clone(),
+                player.dash_cooldown.clone(),
+            ));
+            match &player.dash {
+                Some(dash) => player_entity.insert(dash.clone()),
+                None => player_entity.remove::<Dash>(),
+            };
+            match &player.invulnerable {
+                Some(invulnerable) => player_entity.insert(invulnerable.clone()),
+                None => player_entity.remove::<Invulnerable>(),
+            };
+            if let Some(mut sprite) = player_entity.get_mut::<Sprite>() {
+                sprite.color = player.id.color();
+            }
+        }
+
+        // Players that didn't exist yet at that tick.
+        for entity in player_entities.into_values() {
+            world.despawn(entity);
+        }
+    }
+
+    // Fingerprint of the simulation state, two peers in sync get the same one.
+    pub fn checksum(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        let hash_vec3 = |hasher: &mut DefaultHasher, value: Vec3| {
+            value.to_array().map(f32::to_bits).hash(hasher);
+        };
+
+        self.clock_tick.hash(&mut hasher);
+        self.rng.get_word_pos().hash(&mut hasher);
+        self.score.value.hash(&mut hasher);
+        self.score.players.hash(&mut hasher);
+        self.combo.count.hash(&mut hasher);
+        self.combo.timer.elapsed().hash(&mut hasher);
+        self.enemy_timer.elapsed().hash(&mut hasher);
+        self.star_timer.elapsed().hash(&mut hasher);
+        self.wave.hash(&mut hasher);
+
+        for player in self.players.iter() {
+            player.id.hash(&mut hasher);
+            hash_vec3(&mut hasher, player.transform.translation);
+            hash_vec3(&mut hasher, player.velocity.value);
+            player.lives.value.hash(&mut hasher);
+        }
+        for (transform, enemy) in self.enemies.iter() {
+            hash_vec3(&mut hasher, transform.translation);
+            hash_vec3(&mut hasher, enemy.direction);
+        }
+        for transform in self.stars.iter() {
+            hash_vec3(&mut hasher, transform.translation);
+        }
+        for (transform, boss) in self.bosses.iter() {
+            hash_vec3(&mut hasher, transform.translation);
+            boss.health.hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+}
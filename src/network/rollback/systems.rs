@@ -0,0 +1,261 @@
+use super::resources::*;
+use super::snapshot::WorldSnapshot;
+use super::{MAX_PREDICTION_TICKS, SYNC_TEST_DEPTH};
+use crate::events::SpawnPlayer;
+use crate::game::player::components::{Player, PlayerId, PlayerIntent};
+use crate::game::player::keyboard_intent;
+use crate::game::simulation::resources::GameRng;
+use crate::game::simulation::step_simulation;
+use crate::game::simulation::{MAX_TICKS_PER_FRAME, SIMULATION_TICK_RATE};
+use crate::network::messages::{NetInput, PeerMessage};
+use crate::network::transport::NetTransport;
+use crate::AppState;
+
+use bevy::prelude::*;
+
+// The session skips the menu, both peers go straight into the game.
+pub fn start_session_game(mut commands: Commands, game_rng: Res<GameRng>) {
+    commands.insert_resource(NextState(Some(AppState::Game)));
+    // A desync found by the sync test can be reproduced with the same seed.
+    println!("Session seed: {}", game_rng.seed);
+}
+
+pub fn spawn_session_players(
+    rollback_session: Res<RollbackSession>,
+    mut spawn_player_event_writer: EventWriter<SpawnPlayer>,
+) {
+    for player_id in rollback_session.players() {
+        spawn_player_event_writer.send(SpawnPlayer {
+            id: player_id,
+            remote: true,
+        });
+    }
+}
+
+pub fn end_session(rollback_session: Res<RollbackSession>) {
+    println!("Session over after {} ticks", rollback_session.tick);
+}
+
+// Exchanges the inputs with the peer, rolls back whenever a guess about the remote input
+// turned out wrong and advances the simulation by the ticks due this frame.
+pub fn advance_session(world: &mut World) {
+    let delta_seconds = world.resource::<Time>().delta_seconds();
+
+    world.resource_scope(|world, mut session: Mut<RollbackSession>| {
+        let rollback_tick = receive_peer_inputs(world, &mut session);
+
+        // Wait for the players to spawn, and for the peer to show up.
+        let players_num = world.query::<&Player>().iter(world).count();
+        if players_num < session.players().len() {
+            return;
+        }
+        if !session.started {
+            match session.kind {
+                SessionKind::Peer { address, .. } => {
+                    if let Some(mut net_transport) = world.get_resource_mut::<NetTransport>() {
+                        net_transport.send_message(address, &PeerMessage::Hello);
+                    }
+                    return;
+                }
+                SessionKind::SyncTest => session.started = true,
+            }
+        }
+
+        if let Some(rollback_tick) = rollback_tick {
+            resimulate_from(world, &mut session, rollback_tick);
+        }
+
+        let tick_duration = 1.0 / SIMULATION_TICK_RATE as f32;
+        session.accumulator += delta_seconds;
+        let mut ticks = 0;
+        while session.accumulator >= tick_duration && ticks < MAX_TICKS_PER_FRAME {
+            session.accumulator -= tick_duration;
+            ticks += 1;
+        }
+
+        let keyboard_input = world.resource::<Input<KeyCode>>();
+        let intent = keyboard_intent(
+            keyboard_input,
+            [KeyCode::W, KeyCode::S, KeyCode::A, KeyCode::D],
+            KeyCode::LShift,
+        );
+        session.pending_dash |= intent.dash;
+        let local_input = NetInput {
+            direction: intent.direction.truncate().into(),
+            dash: session.pending_dash,
+        };
+
+        for _ in 0..ticks {
+            // Too far ahead of the peer, wait for its inputs instead of guessing even more.
+            if matches!(session.kind, SessionKind::Peer { .. })
+                && session.tick >= session.remote_received_until + MAX_PREDICTION_TICKS
+            {
+                session.accumulator = 0.0;
+                break;
+            }
+
+            let tick = session.tick;
+            // The dash key press belongs to the first tick only.
+            let dash = std::mem::take(&mut session.pending_dash);
+            session.local_inputs.insert(
+                tick,
+                NetInput {
+                    dash,
+                    ..local_input
+                },
+            );
+
+            simulate_tick(world, &mut session, false);
+
+            if matches!(session.kind, SessionKind::SyncTest) && tick >= SYNC_TEST_DEPTH {
+                resimulate_from(world, &mut session, tick + 1 - SYNC_TEST_DEPTH);
+            }
+        }
+
+        send_local_inputs(world, &session);
+
+        let keep_from = match session.kind {
+            SessionKind::Peer { .. } => session.remote_received_until.min(session.tick),
+            SessionKind::SyncTest => session.tick.saturating_sub(SYNC_TEST_DEPTH),
+        };
+        session.prune(keep_from);
+    });
+}
+
+// Stores the inputs received from the peer. Returns the earliest tick that was simulated
+// with a wrong guess and has to be simulated again.
+fn receive_peer_inputs(world: &mut World, session: &mut RollbackSession) -> Option<u64> {
+    let peer_address = match session.kind {
+        SessionKind::Peer { address, .. } => address,
+        SessionKind::SyncTest => return None,
+    };
+    let mut net_transport = world.get_resource_mut::<NetTransport>()?;
+    let mut rollback_tick: Option<u64> = None;
+
+    while let Some((address, message)) = net_transport.receive_message::<PeerMessage>() {
+        if address != peer_address {
+            continue;
+        }
+
+        // Anything from the peer means it's there and ready to play.
+        session.started = true;
+
+        let (first_tick, inputs, received_until) = match message {
+            PeerMessage::Hello => continue,
+            PeerMessage::Inputs {
+                first_tick,
+                inputs,
+                received_until,
+            } => (first_tick, inputs, received_until),
+        };
+        session.peer_received_until = session.peer_received_until.max(received_until);
+
+        // Inputs past a gap are useless, they're sent again until confirmed anyway.
+        if first_tick > session.remote_received_until {
+            continue;
+        }
+
+        for (index, input) in inputs.into_iter().enumerate() {
+            let tick = first_tick + index as u64;
+            if tick < session.remote_received_until {
+                continue;
+            }
+
+            session.remote_inputs.insert(tick, input);
+            session.remote_received_until = tick + 1;
+
+            if let Some(predicted_input) = session.predicted_inputs.remove(&tick) {
+                if predicted_input != input {
+                    rollback_tick = Some(rollback_tick.map_or(tick, |rollback| rollback.min(tick)));
+                }
+            }
+        }
+    }
+
+    rollback_tick
+}
+
+fn send_local_inputs(world: &mut World, session: &RollbackSession) {
+    let peer_address = match session.kind {
+        SessionKind::Peer { address, .. } => address,
+        SessionKind::SyncTest => return,
+    };
+    let mut net_transport = match world.get_resource_mut::<NetTransport>() {
+        Some(v) => v,
+        None => return,
+    };
+
+    let first_tick = session.peer_received_until;
+    let inputs: Vec<NetInput> = session
+        .local_inputs
+        .range(first_tick..)
+        .map(|(_, input)| *input)
+        .collect();
+
+    net_transport.send_message(
+        peer_address,
+        &PeerMessage::Inputs {
+            first_tick,
+            inputs,
+            received_until: session.remote_received_until,
+        },
+    );
+}
+
+// Goes back to the state before the given tick and simulates up to the present again.
+fn resimulate_from(world: &mut World, session: &mut RollbackSession, tick: u64) {
+    let snapshot = match session.snapshots.get(&tick) {
+        Some(v) => v.clone(),
+        None => {
+            warn!("No snapshot of tick {} to roll back to", tick);
+            return;
+        }
+    };
+
+    let present_tick = session.tick;
+    snapshot.restore(world);
+    session.tick = tick;
+
+    while session.tick < present_tick {
+        simulate_tick(world, session, true);
+    }
+}
+
+// Feeds the inputs of the tick to the players and simulates it.
+fn simulate_tick(world: &mut World, session: &mut RollbackSession, resimulating: bool) {
+    let tick = session.tick;
+    session.snapshots.insert(tick, WorldSnapshot::save(world));
+
+    let mut inputs: Vec<(PlayerId, NetInput)> = vec![(
+        session.local_player(),
+        session.local_inputs.get(&tick).copied().unwrap_or_default(),
+    )];
+    if let Some(remote_player) = session.remote_player() {
+        inputs.push((remote_player, session.remote_input(tick)));
+    }
+
+    for (player, mut intent) in world
+        .query::<(&Player, &mut PlayerIntent)>()
+        .iter_mut(world)
+    {
+        if let Some((_, input)) = inputs.iter().find(|(player_id, _)| *player_id == player.id) {
+            intent.direction = Vec2::from(input.direction).extend(0.0);
+            intent.dash = input.dash;
+        }
+    }
+
+    step_simulation(world, resimulating);
+    session.tick += 1;
+
+    if !matches!(session.kind, SessionKind::SyncTest) {
+        return;
+    }
+
+    // The same inputs from the same state have to lead to the same result every time.
+    let checksum = WorldSnapshot::save(world).checksum();
+    if let Some(previous_checksum) = session.checksums.insert(tick, checksum) {
+        if previous_checksum != checksum {
+            error!("Desync detected on tick {}!", tick);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+pub mod resources;
+mod systems;
+
+use super::headless_plugins;
+use super::transport::NetTransport;
+use crate::game::player::resources::PlayerCount;
+use crate::game::GamePlugin;
+use crate::systems::handle_game_over_event;
+use crate::AppState;
+use resources::*;
+use systems::*;
+
+use bevy::app::{ScheduleRunnerPlugin, ScheduleRunnerSettings};
+use bevy::prelude::*;
+use std::time::Duration;
+
+pub const SERVER_TICK_RATE: f64 = 60.0;
+
+// Runs the game authoritatively, the clients only send their input and draw the snapshots.
+pub struct ServerPlugin;
+
+impl Plugin for ServerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConnectedClients>()
+            .init_resource::<SnapshotTimer>()
+            .add_startup_system(start_server_game)
+            .add_system(respawn_connected_players.in_schedule(OnEnter(AppState::Game)))
+            .add_system(restart_server_game.in_schedule(OnEnter(AppState::GameOver)))
+            // Inputs are applied before the game systems get to run this frame.
+            .add_system(
+                receive_client_messages
+                    .in_base_set(CoreSet::PreUpdate)
+                    .run_if(in_state(AppState::Game)),
+            )
+            .add_systems(
+                (drop_timed_out_clients, broadcast_snapshot).in_set(OnUpdate(AppState::Game)),
+            );
+    }
+}
+
+// Headless app running the whole game simulation for the connected clients.
+pub fn server_app(net_transport: NetTransport) -> App {
+    let mut app = App::new();
+
+    app.insert_resource(ScheduleRunnerSettings::run_loop(Duration::from_secs_f64(
+        1.0 / SERVER_TICK_RATE,
+    )))
+    .add_plugins(headless_plugins())
+    .add_plugin(ScheduleRunnerPlugin)
+    .add_state::<AppState>()
+    .add_plugin(GamePlugin)
+    .add_plugin(ServerPlugin)
+    .insert_resource(net_transport)
+    // Players only join over the network.
+    .insert_resource(PlayerCount { value: 0 })
+    .add_system(handle_game_over_event);
+
+    app
+}
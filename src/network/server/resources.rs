@@ -0,0 +1,44 @@
+use crate::game::player::components::PlayerId;
+use crate::network::SNAPSHOT_PERIOD;
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+pub struct ConnectedClient {
+    pub player_id: PlayerId,
+    // Time of the last message from the client, used to drop silent clients.
+    pub last_heard: f32,
+}
+
+#[derive(Resource, Default)]
+pub struct ConnectedClients {
+    pub clients: HashMap<SocketAddr, ConnectedClient>,
+}
+
+impl ConnectedClients {
+    // Lowest player id nobody is using yet.
+    pub fn free_player_id(&self, max_players: usize) -> Option<PlayerId> {
+        (0..max_players).map(PlayerId).find(|player_id| {
+            !self
+                .clients
+                .values()
+                .any(|client| client.player_id == *player_id)
+        })
+    }
+}
+
+#[derive(Resource)]
+pub struct SnapshotTimer {
+    pub timer: Timer,
+    pub tick: u64,
+}
+
+impl Default for SnapshotTimer {
+    fn default() -> Self {
+        SnapshotTimer {
+            timer: Timer::from_seconds(SNAPSHOT_PERIOD, TimerMode::Repeating),
+            tick: 0,
+        }
+    }
+}
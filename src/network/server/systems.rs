@@ -0,0 +1,198 @@
+use super::resources::*;
+use crate::events::SpawnPlayer;
+use crate::game::boss::components::Boss;
+use crate::game::enemy::components::Enemy;
+use crate::game::player::components::{Player, PlayerIntent};
+use crate::game::player::MAX_PLAYERS;
+use crate::game::score::resources::Score;
+use crate::game::star::components::Star;
+use crate::network::messages::*;
+use crate::network::transport::NetTransport;
+use crate::network::CONNECTION_TIMEOUT;
+use crate::AppState;
+
+use bevy::prelude::*;
+
+// The server has no menu, it goes straight into the game.
+pub fn start_server_game(mut commands: Commands) {
+    commands.insert_resource(NextState(Some(AppState::Game)));
+}
+
+// After a game over the next run starts right away for everyone still connected.
+pub fn restart_server_game(mut commands: Commands) {
+    println!("Restarting the game!");
+    commands.insert_resource(NextState(Some(AppState::Game)));
+}
+
+// Brings back the players of the connected clients when a new run starts.
+pub fn respawn_connected_players(
+    connected_clients: Res<ConnectedClients>,
+    mut spawn_player_event_writer: EventWriter<SpawnPlayer>,
+) {
+    for client in connected_clients.clients.values() {
+        spawn_player_event_writer.send(SpawnPlayer {
+            id: client.player_id,
+            remote: true,
+        });
+    }
+}
+
+// Handles the clients joining, leaving and sending their input.
+pub fn receive_client_messages(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &Player, &mut PlayerIntent)>,
+    mut spawn_player_event_writer: EventWriter<SpawnPlayer>,
+    mut net_transport: ResMut<NetTransport>,
+    mut connected_clients: ResMut<ConnectedClients>,
+    time: Res<Time>,
+) {
+    while let Some((address, message)) = net_transport.receive_message::<ClientMessage>() {
+        match message {
+            ClientMessage::Connect => {
+                // The welcome got lost, the client is asking again.
+                if let Some(client) = connected_clients.clients.get(&address) {
+                    let player_id = client.player_id.0;
+                    net_transport.send_message(address, &ServerMessage::Welcome { player_id });
+                    continue;
+                }
+
+                let player_id = match connected_clients.free_player_id(MAX_PLAYERS) {
+                    Some(v) => v,
+                    None => {
+                        net_transport.send_message(address, &ServerMessage::Full);
+                        continue;
+                    }
+                };
+
+                connected_clients.clients.insert(
+                    address,
+                    ConnectedClient {
+                        player_id,
+                        last_heard: time.elapsed_seconds(),
+                    },
+                );
+                spawn_player_event_writer.send(SpawnPlayer {
+                    id: player_id,
+                    remote: true,
+                });
+                net_transport.send_message(
+                    address,
+                    &ServerMessage::Welcome {
+                        player_id: player_id.0,
+                    },
+                );
+                println!("Player {} connected from {}", player_id.0 + 1, address);
+            }
+            ClientMessage::Input { direction, dash } => {
+                let client = match connected_clients.clients.get_mut(&address) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                client.last_heard = time.elapsed_seconds();
+
+                for (_, player, mut intent) in player_query.iter_mut() {
+                    if player.id != client.player_id {
+                        continue;
+                    }
+                    intent.direction = Vec2::from(direction).extend(0.0).clamp_length_max(1.0);
+                    // Several inputs can arrive before the next tick, don't let a later one eat the dash.
+                    intent.dash |= dash;
+                }
+            }
+            ClientMessage::Disconnect => {
+                let client = match connected_clients.clients.remove(&address) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                despawn_client_player(&mut commands, &player_query, &client);
+                println!("Player {} disconnected", client.player_id.0 + 1);
+            }
+        }
+    }
+}
+
+// Drops the clients that went silent without saying goodbye.
+pub fn drop_timed_out_clients(
+    mut commands: Commands,
+    player_query: Query<(Entity, &Player, &mut PlayerIntent)>,
+    mut connected_clients: ResMut<ConnectedClients>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_seconds();
+
+    connected_clients.clients.retain(|_, client| {
+        if now - client.last_heard < CONNECTION_TIMEOUT {
+            return true;
+        }
+
+        despawn_client_player(&mut commands, &player_query, client);
+        println!("Player {} timed out", client.player_id.0 + 1);
+        false
+    });
+}
+
+fn despawn_client_player(
+    commands: &mut Commands,
+    player_query: &Query<(Entity, &Player, &mut PlayerIntent)>,
+    client: &ConnectedClient,
+) {
+    for (player_entity, player, _) in player_query.iter() {
+        if player.id == client.player_id {
+            commands.entity(player_entity).despawn();
+        }
+    }
+}
+
+// Sends the state of the world to every client.
+#[allow(clippy::too_many_arguments)]
+pub fn broadcast_snapshot(
+    player_query: Query<(Entity, &Transform, &Player)>,
+    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+    star_query: Query<(Entity, &Transform), With<Star>>,
+    boss_query: Query<(Entity, &Transform), With<Boss>>,
+    mut net_transport: ResMut<NetTransport>,
+    mut snapshot_timer: ResMut<SnapshotTimer>,
+    connected_clients: Res<ConnectedClients>,
+    score: Res<Score>,
+    time: Res<Time>,
+) {
+    snapshot_timer.timer.tick(time.delta());
+    if !snapshot_timer.timer.just_finished() {
+        return;
+    }
+    snapshot_timer.tick += 1;
+
+    let net_entity = |entity: Entity, kind: NetEntityKind, transform: &Transform| NetEntity {
+        net_id: entity.to_bits(),
+        kind,
+        position: transform.translation.truncate().into(),
+    };
+
+    let mut entities: Vec<NetEntity> = Vec::new();
+    for (entity, transform, player) in player_query.iter() {
+        entities.push(net_entity(
+            entity,
+            NetEntityKind::Player(player.id.0),
+            transform,
+        ));
+    }
+    for (entity, transform) in enemy_query.iter() {
+        entities.push(net_entity(entity, NetEntityKind::Enemy, transform));
+    }
+    for (entity, transform) in star_query.iter() {
+        entities.push(net_entity(entity, NetEntityKind::Star, transform));
+    }
+    for (entity, transform) in boss_query.iter() {
+        entities.push(net_entity(entity, NetEntityKind::Boss, transform));
+    }
+
+    let snapshot = ServerMessage::Snapshot(Snapshot {
+        tick: snapshot_timer.tick,
+        entities,
+        scores: score.players.clone(),
+    });
+
+    for address in connected_clients.clients.keys() {
+        net_transport.send_message(*address, &snapshot);
+    }
+}
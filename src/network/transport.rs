@@ -0,0 +1,125 @@
+use super::MAX_PACKET_SIZE;
+
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::{Arc, Mutex};
+
+// Unreliable datagram delivery, either over UDP or in memory.
+pub trait Transport: Send + Sync {
+    fn send(&mut self, to: SocketAddr, bytes: Vec<u8>);
+    // Returns the next datagram that arrived, without blocking.
+    fn receive(&mut self) -> Option<(SocketAddr, Vec<u8>)>;
+}
+
+pub struct UdpTransport {
+    socket: UdpSocket,
+    buffer: Vec<u8>,
+}
+
+impl UdpTransport {
+    pub fn bind(address: SocketAddr) -> io::Result<UdpTransport> {
+        let socket = UdpSocket::bind(address)?;
+        socket.set_nonblocking(true)?;
+
+        Ok(UdpTransport {
+            socket,
+            buffer: vec![0; MAX_PACKET_SIZE],
+        })
+    }
+}
+
+impl Transport for UdpTransport {
+    fn send(&mut self, to: SocketAddr, bytes: Vec<u8>) {
+        // Datagrams may get lost anyway, the protocol doesn't rely on every one arriving.
+        if let Err(error) = self.socket.send_to(&bytes, to) {
+            warn!("Failed to send a packet to {}: {}", to, error);
+        }
+    }
+
+    fn receive(&mut self) -> Option<(SocketAddr, Vec<u8>)> {
+        loop {
+            match self.socket.recv_from(&mut self.buffer) {
+                Ok((size, from)) => return Some((from, self.buffer[..size].to_vec())),
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => return None,
+                // E.g. the other side's port is closed, skip to the next packet.
+                Err(_) => continue,
+            }
+        }
+    }
+}
+
+type Inboxes = HashMap<SocketAddr, VecDeque<(SocketAddr, Vec<u8>)>>;
+
+// In-memory network shared by the loopback transports, packets are never lost.
+#[derive(Clone, Default)]
+pub struct LoopbackNetwork {
+    inboxes: Arc<Mutex<Inboxes>>,
+}
+
+impl LoopbackNetwork {
+    pub fn endpoint(&self, address: SocketAddr) -> LoopbackTransport {
+        self.inboxes.lock().unwrap().entry(address).or_default();
+
+        LoopbackTransport {
+            address,
+            network: self.clone(),
+        }
+    }
+}
+
+pub struct LoopbackTransport {
+    address: SocketAddr,
+    network: LoopbackNetwork,
+}
+
+impl Transport for LoopbackTransport {
+    fn send(&mut self, to: SocketAddr, bytes: Vec<u8>) {
+        // Like UDP, sending to nobody just drops the packet.
+        if let Some(inbox) = self.network.inboxes.lock().unwrap().get_mut(&to) {
+            inbox.push_back((self.address, bytes));
+        }
+    }
+
+    fn receive(&mut self) -> Option<(SocketAddr, Vec<u8>)> {
+        self.network
+            .inboxes
+            .lock()
+            .unwrap()
+            .get_mut(&self.address)
+            .and_then(|inbox| inbox.pop_front())
+    }
+}
+
+#[derive(Resource)]
+pub struct NetTransport {
+    pub transport: Box<dyn Transport>,
+}
+
+impl NetTransport {
+    pub fn new(transport: impl Transport + 'static) -> NetTransport {
+        NetTransport {
+            transport: Box::new(transport),
+        }
+    }
+
+    pub fn send_message<T: Serialize>(&mut self, to: SocketAddr, message: &T) {
+        match bincode::serialize(message) {
+            Ok(bytes) => self.transport.send(to, bytes),
+            Err(error) => warn!("Failed to serialize a message: {}", error),
+        }
+    }
+
+    // Returns the next message that could be decoded, garbage packets are skipped.
+    pub fn receive_message<T: DeserializeOwned>(&mut self) -> Option<(SocketAddr, T)> {
+        while let Some((from, bytes)) = self.transport.receive() {
+            if let Ok(message) = bincode::deserialize(&bytes) {
+                return Some((from, message));
+            }
+        }
+        None
+    }
+}
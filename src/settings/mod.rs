@@ -0,0 +1,27 @@
+pub mod resources;
+mod systems;
+
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const SETTINGS_PATH: &str = "settings.ron";
+pub const VOLUME_STEP: f32 = 0.1;
+pub const SCREEN_SHAKE_STEP: f32 = 0.25;
+pub const RESOLUTIONS: [(f32, f32); 4] = [
+    (1280.0, 720.0),
+    (1600.0, 900.0),
+    (1920.0, 1080.0),
+    (2560.0, 1440.0),
+];
+
+// Applies the settings whenever they change. The settings themselves are read in main,
+// the window has to be created with them.
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(apply_window_settings)
+            .add_system(apply_binding_settings);
+    }
+}
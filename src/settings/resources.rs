@@ -0,0 +1,278 @@
+use super::{RESOLUTIONS, SCREEN_SHAKE_STEP, SETTINGS_PATH, VOLUME_STEP};
+use crate::game::player::resources::InputBinding;
+use crate::game::player::MAX_PLAYERS;
+
+use bevy::prelude::*;
+use bevy::window::{PresentMode, WindowMode, WindowResolution};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    pub fn window_mode(&self) -> WindowMode {
+        match self {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+            DisplayMode::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+// One line of the settings screen.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Setting {
+    MasterVolume,
+    SfxVolume,
+    MusicVolume,
+    UiVolume,
+    DisplayMode,
+    Resolution,
+    Vsync,
+    ScreenShake,
+    Ghost,
+    // Input binding of the player with the given id.
+    Binding(usize),
+}
+
+impl Setting {
+    pub fn all() -> Vec<Setting> {
+        let mut settings = vec![
+            Setting::MasterVolume,
+            Setting::SfxVolume,
+            Setting::MusicVolume,
+            Setting::UiVolume,
+            Setting::DisplayMode,
+            Setting::Resolution,
+            Setting::Vsync,
+            Setting::ScreenShake,
+            Setting::Ghost,
+        ];
+        settings.extend((0..MAX_PLAYERS).map(Setting::Binding));
+        settings
+    }
+}
+
+// Audio, video and gameplay options, persisted to SETTINGS_PATH.
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub master_volume: f32,
+    pub sfx_volume: f32,
+    pub music_volume: f32,
+    pub ui_volume: f32,
+    pub display_mode: DisplayMode,
+    pub resolution: (f32, f32),
+    pub vsync: bool,
+    // How strong the camera effects are: the shaking, the zoom punches and the hit-stops.
+    // 0 turns them all off.
+    pub screen_shake: f32,
+    // Whether solo runs race the ghost of the best run.
+    pub ghost: bool,
+    pub bindings: [InputBinding; MAX_PLAYERS],
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            master_volume: 1.0,
+            sfx_volume: 1.0,
+            music_volume: 0.7,
+            ui_volume: 1.0,
+            display_mode: DisplayMode::Windowed,
+            resolution: RESOLUTIONS[0],
+            vsync: true,
+            screen_shake: 1.0,
+            ghost: true,
+            bindings: [
+                InputBinding::Wasd,
+                InputBinding::Arrows,
+                InputBinding::Gamepad(0),
+                InputBinding::Gamepad(1),
+            ],
+        }
+    }
+}
+
+impl Settings {
+    // Reads the settings, the defaults are used before the first launch.
+    pub fn load() -> Self {
+        let settings = match std::fs::read_to_string(SETTINGS_PATH) {
+            Ok(v) => v,
+            Err(_) => return Settings::default(),
+        };
+
+        match ron::from_str::<Settings>(&settings) {
+            Ok(v) => v,
+            Err(error) => {
+                println!("Could not parse {}: {}", SETTINGS_PATH, error);
+                Settings::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let settings = match ron::ser::to_string_pretty(self, default()) {
+            Ok(v) => v,
+            Err(error) => {
+                println!("Could not serialize the settings: {}", error);
+                return;
+            }
+        };
+
+        if let Err(error) = std::fs::write(SETTINGS_PATH, settings) {
+            println!("Could not write {}: {}", SETTINGS_PATH, error);
+        }
+    }
+
+    pub fn window(&self) -> Window {
+        Window {
+            mode: self.display_mode.window_mode(),
+            resolution: WindowResolution::new(self.resolution.0, self.resolution.1),
+            present_mode: self.present_mode(),
+            ..default()
+        }
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        if self.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        }
+    }
+
+    // Moves the setting one step forwards or backwards, wrapping around the choices.
+    pub fn change(&mut self, setting: Setting, forwards: bool) {
+        let step_volume = |volume: f32| {
+            let step = if forwards { VOLUME_STEP } else { -VOLUME_STEP };
+            // Rounded, so the steps don't drift away from the round numbers.
+            ((volume + step).clamp(0.0, 1.0) / VOLUME_STEP).round() * VOLUME_STEP
+        };
+
+        match setting {
+            Setting::MasterVolume => self.master_volume = step_volume(self.master_volume),
+            Setting::SfxVolume => self.sfx_volume = step_volume(self.sfx_volume),
+            Setting::MusicVolume => self.music_volume = step_volume(self.music_volume),
+            Setting::UiVolume => self.ui_volume = step_volume(self.ui_volume),
+            Setting::DisplayMode => {
+                let modes = [
+                    DisplayMode::Windowed,
+                    DisplayMode::Borderless,
+                    DisplayMode::Fullscreen,
+                ];
+                self.display_mode = cycle(&modes, &self.display_mode, forwards);
+            }
+            Setting::Resolution => {
+                self.resolution = cycle(&RESOLUTIONS, &self.resolution, forwards);
+            }
+            Setting::Vsync => self.vsync = !self.vsync,
+            Setting::ScreenShake => {
+                let step = if forwards {
+                    SCREEN_SHAKE_STEP
+                } else {
+                    -SCREEN_SHAKE_STEP
+                };
+                self.screen_shake = (self.screen_shake + step).clamp(0.0, 1.0);
+            }
+            Setting::Ghost => self.ghost = !self.ghost,
+            Setting::Binding(player) => {
+                let bindings = binding_choices();
+                self.bindings[player] = cycle(&bindings, &self.bindings[player], forwards);
+            }
+        }
+    }
+
+    // How far along its choices the setting is, from 0 to 1. Drawn as a bar.
+    pub fn fraction(&self, setting: Setting) -> f32 {
+        let position =
+            |index: Option<usize>, len: usize| index.unwrap_or(0) as f32 / (len - 1).max(1) as f32;
+
+        match setting {
+            Setting::MasterVolume => self.master_volume,
+            Setting::SfxVolume => self.sfx_volume,
+            Setting::MusicVolume => self.music_volume,
+            Setting::UiVolume => self.ui_volume,
+            Setting::DisplayMode => position(Some(self.display_mode as usize), 3),
+            Setting::Resolution => position(
+                RESOLUTIONS
+                    .iter()
+                    .position(|resolution| *resolution == self.resolution),
+                RESOLUTIONS.len(),
+            ),
+            Setting::Vsync => {
+                if self.vsync {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Setting::ScreenShake => self.screen_shake,
+            Setting::Ghost => {
+                if self.ghost {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Setting::Binding(player) => {
+                let bindings = binding_choices();
+                position(
+                    bindings
+                        .iter()
+                        .position(|binding| *binding == self.bindings[player]),
+                    bindings.len(),
+                )
+            }
+        }
+    }
+
+    pub fn describe(&self, setting: Setting) -> String {
+        match setting {
+            Setting::MasterVolume => format!("Master volume: {:.0}%", self.master_volume * 100.0),
+            Setting::SfxVolume => format!("Sound effects volume: {:.0}%", self.sfx_volume * 100.0),
+            Setting::MusicVolume => format!("Music volume: {:.0}%", self.music_volume * 100.0),
+            Setting::UiVolume => format!("Interface volume: {:.0}%", self.ui_volume * 100.0),
+            Setting::DisplayMode => format!("Window mode: {:?}", self.display_mode),
+            Setting::Resolution => {
+                format!("Resolution: {}x{}", self.resolution.0, self.resolution.1)
+            }
+            Setting::Vsync => format!("Vsync: {}", if self.vsync { "on" } else { "off" }),
+            Setting::ScreenShake => format!("Camera effects: {:.0}%", self.screen_shake * 100.0),
+            Setting::Ghost => format!("Ghost racer: {}", if self.ghost { "on" } else { "off" }),
+            Setting::Binding(player) => {
+                format!(
+                    "Player {} controls: {:?}",
+                    player + 1,
+                    self.bindings[player]
+                )
+            }
+        }
+    }
+}
+
+fn binding_choices() -> Vec<InputBinding> {
+    let mut bindings = vec![InputBinding::Wasd, InputBinding::Arrows];
+    bindings.extend((0..MAX_PLAYERS).map(InputBinding::Gamepad));
+    bindings
+}
+
+// Next or previous choice after the current one. A value that isn't among the choices,
+// like a resolution typed into the file by hand, starts over from the first one.
+fn cycle<T: Copy + PartialEq>(choices: &[T], current: &T, forwards: bool) -> T {
+    let index = match choices.iter().position(|choice| choice == current) {
+        Some(v) => v,
+        None => return choices[0],
+    };
+
+    if forwards {
+        choices[(index + 1) % choices.len()]
+    } else {
+        choices[(index + choices.len() - 1) % choices.len()]
+    }
+}
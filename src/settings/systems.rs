@@ -0,0 +1,40 @@
+use super::resources::*;
+use crate::game::player::resources::PlayerBindings;
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+pub fn apply_window_settings(
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    settings: Res<Settings>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let mut window = match window_query.get_single_mut() {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    let window_mode = settings.display_mode.window_mode();
+    if window.mode != window_mode {
+        window.mode = window_mode;
+    }
+    if (window.resolution.width(), window.resolution.height()) != settings.resolution {
+        window
+            .resolution
+            .set(settings.resolution.0, settings.resolution.1);
+    }
+    if window.present_mode != settings.present_mode() {
+        window.present_mode = settings.present_mode();
+    }
+}
+
+pub fn apply_binding_settings(
+    mut player_bindings: ResMut<PlayerBindings>,
+    settings: Res<Settings>,
+) {
+    if settings.is_changed() {
+        player_bindings.bindings = settings.bindings;
+    }
+}
@@ -1,17 +1,26 @@
 use super::events::GameOver;
+use super::game::camera::resources::CameraShake;
+use super::game::{GameState, ARENA_HEIGHT, ARENA_WIDTH};
 use super::AppState;
 
 use bevy::app::AppExit;
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
+use bevy::render::camera::ScalingMode;
 
-pub fn spawn_camera(mut commands: Commands, window_query: Query<&Window, With<PrimaryWindow>>) {
-    let window = window_query.get_single().unwrap();
+const GAME_OVER_DELAY: f32 = 1.0; // Long enough for the death burst to fade out.
 
-    commands.spawn(Camera2dBundle {
-        transform: Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, 0.0),
+// Looks at the whole arena, however big the window is.
+pub fn spawn_camera(mut commands: Commands) {
+    let mut camera_bundle = Camera2dBundle {
+        transform: Transform::from_xyz(ARENA_WIDTH / 2.0, ARENA_HEIGHT / 2.0, 0.0),
         ..default()
-    });
+    };
+    camera_bundle.projection.scaling_mode = ScalingMode::AutoMin {
+        min_width: ARENA_WIDTH,
+        min_height: ARENA_HEIGHT,
+    };
+
+    commands.spawn(camera_bundle);
 }
 
 pub fn transition_to_game_state(
@@ -49,14 +58,43 @@ pub fn close_game(
     }
 }
 
+// Announces the end of the run, and switches to the game over screen once the last death's
+// hit-stop and burst have played out.
 pub fn handle_game_over_event(
     mut commands: Commands,
     mut game_over_event_reader: EventReader<GameOver>,
+    mut game_over_delay: Local<Option<Timer>>,
+    app_state: Res<State<AppState>>,
+    game_state: Res<State<GameState>>,
+    camera_shake: Res<CameraShake>,
+    time: Res<Time>,
 ) {
+    // Left the game some other way while waiting.
+    if app_state.0 != AppState::Game {
+        *game_over_delay = None;
+    }
+
+    if let Some(timer) = game_over_delay.as_mut() {
+        if game_state.0 == GameState::Running {
+            timer.tick(time.delta());
+        }
+        if timer.finished() && camera_shake.hit_stop <= 0.0 {
+            *game_over_delay = None;
+            commands.insert_resource(NextState(Some(AppState::GameOver)));
+        }
+    }
+
     for event in game_over_event_reader.iter() {
         println!("Game Over!");
         println!("Final score: {}", event.score_value);
-        commands.insert_resource(NextState(Some(AppState::GameOver)));
+        for player_result in event.players.iter() {
+            println!(
+                "Player {} score: {}",
+                player_result.id.0 + 1,
+                player_result.score
+            );
+        }
+        *game_over_delay = Some(Timer::from_seconds(GAME_OVER_DELAY, TimerMode::Once));
         return;
     }
 }
@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+// Column in the corner of the screen the unlock toasts are stacked in.
+#[derive(Component)]
+pub struct ToastStack {}
+
+#[derive(Component)]
+pub struct AchievementToast {
+    pub timer: Timer,
+}
+
+// Grid of every achievement, opened from the menu.
+#[derive(Component)]
+pub struct AchievementsScreen {}
@@ -0,0 +1,26 @@
+mod components;
+mod systems;
+
+use crate::AppState;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const TOAST_DURATION: f32 = 3.0;
+pub const TOAST_FADE_DURATION: f32 = 0.5; // The toast fades out during the last part of its time.
+pub const TOAST_SIZE: f32 = 64.0;
+pub const ACHIEVEMENT_ICON_SIZE: f32 = 64.0;
+pub const ACHIEVEMENT_SPACING: f32 = 16.0;
+pub const ACHIEVEMENT_BAR_HEIGHT: f32 = 6.0;
+
+pub struct AchievementsUIPlugin;
+
+impl Plugin for AchievementsUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(spawn_toast_stack)
+            .add_system(spawn_achievement_toasts)
+            .add_system(update_achievement_toasts)
+            .add_system(toggle_achievements_screen.run_if(in_state(AppState::Menu)))
+            .add_system(despawn_achievements_screen.in_schedule(OnExit(AppState::Menu)));
+    }
+}
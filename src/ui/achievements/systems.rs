@@ -0,0 +1,253 @@
+use super::components::*;
+use super::{
+    ACHIEVEMENT_BAR_HEIGHT, ACHIEVEMENT_ICON_SIZE, ACHIEVEMENT_SPACING, TOAST_DURATION,
+    TOAST_FADE_DURATION, TOAST_SIZE,
+};
+use crate::events::{AchievementUnlocked, Sound, SoundEvent};
+use crate::game::achievement::resources::{AchievementProgress, Achievements};
+
+use bevy::prelude::*;
+
+pub fn spawn_toast_stack(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    right: Val::Px(ACHIEVEMENT_SPACING),
+                    top: Val::Px(ACHIEVEMENT_SPACING),
+                    ..default()
+                },
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            ..default()
+        },
+        ToastStack {},
+    ));
+}
+
+// Pops up the icon of every unlocked achievement on a yellow button.
+pub fn spawn_achievement_toasts(
+    mut commands: Commands,
+    mut achievement_unlocked_event_reader: EventReader<AchievementUnlocked>,
+    toast_stack_query: Query<Entity, With<ToastStack>>,
+    asset_server: Res<AssetServer>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+) {
+    let toast_stack_entity = match toast_stack_query.get_single() {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    for event in achievement_unlocked_event_reader.iter() {
+        commands.entity(toast_stack_entity).with_children(|parent| {
+            parent
+                .spawn((
+                    ImageBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(TOAST_SIZE), Val::Px(TOAST_SIZE)),
+                            margin: UiRect::bottom(Val::Px(ACHIEVEMENT_SPACING / 2.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        image: asset_server
+                            .load("sprites/Default/button_yellow.png")
+                            .into(),
+                        ..default()
+                    },
+                    AchievementToast {
+                        timer: Timer::from_seconds(TOAST_DURATION, TimerMode::Once),
+                    },
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(TOAST_SIZE / 2.0), Val::Px(TOAST_SIZE / 2.0)),
+                            ..default()
+                        },
+                        image: asset_server.load(event.icon.as_str()).into(),
+                        ..default()
+                    });
+                });
+        });
+
+        sound_event_writer.send(Sound::AchievementUnlocked.into());
+    }
+}
+
+// Fades the toasts out and removes them once their time is up.
+pub fn update_achievement_toasts(
+    mut commands: Commands,
+    mut toast_query: Query<(Entity, &mut AchievementToast, &Children)>,
+    mut image_query: Query<&mut BackgroundColor>,
+    time: Res<Time>,
+) {
+    for (toast_entity, mut toast, children) in toast_query.iter_mut() {
+        toast.timer.tick(time.delta());
+
+        if toast.timer.finished() {
+            commands.entity(toast_entity).despawn_recursive();
+            continue;
+        }
+
+        let alpha = (toast.timer.remaining_secs() / TOAST_FADE_DURATION).min(1.0);
+        for entity in std::iter::once(toast_entity).chain(children.iter().copied()) {
+            if let Ok(mut background_color) = image_query.get_mut(entity) {
+                background_color.0.set_a(alpha);
+            }
+        }
+    }
+}
+
+// Opens or closes the achievements screen. Unlocked achievements sit on yellow buttons,
+// locked ones are greyed out, with a bar for the ones counted over many runs.
+pub fn toggle_achievements_screen(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    screen_query: Query<Entity, With<AchievementsScreen>>,
+    asset_server: Res<AssetServer>,
+    achievements: Res<Achievements>,
+    achievement_progress: Res<AchievementProgress>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::A) {
+        return;
+    }
+
+    if let Ok(screen_entity) = screen_query.get_single() {
+        commands.entity(screen_entity).despawn_recursive();
+        return;
+    }
+
+    println!(
+        "Achievements: {}/{} unlocked",
+        achievement_progress.unlocked.len(),
+        achievements.definitions.len()
+    );
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    align_content: AlignContent::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+                ..default()
+            },
+            AchievementsScreen {},
+        ))
+        .with_children(|parent| {
+            for definition in achievements.definitions.iter() {
+                let unlocked = achievement_progress.is_unlocked(&definition.id);
+                let lifetime_progress = definition
+                    .condition
+                    .lifetime_progress(&achievement_progress);
+
+                let (button_texture, icon_color) = if unlocked {
+                    ("sprites/Default/button_yellow.png", Color::WHITE)
+                } else {
+                    (
+                        "sprites/Default/button_grey.png",
+                        Color::rgba(0.2, 0.2, 0.2, 0.8),
+                    )
+                };
+
+                match (unlocked, lifetime_progress) {
+                    (false, Some((current, target))) => println!(
+                        "[ ] {} - {} ({}/{})",
+                        definition.name, definition.description, current, target
+                    ),
+                    (false, None) => {
+                        println!("[ ] {} - {}", definition.name, definition.description)
+                    }
+                    (true, _) => println!("[x] {} - {}", definition.name, definition.description),
+                }
+
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            margin: UiRect::all(Val::Px(ACHIEVEMENT_SPACING)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent
+                            .spawn(ImageBundle {
+                                style: Style {
+                                    size: Size::new(
+                                        Val::Px(ACHIEVEMENT_ICON_SIZE),
+                                        Val::Px(ACHIEVEMENT_ICON_SIZE),
+                                    ),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                image: asset_server.load(button_texture).into(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(ImageBundle {
+                                    style: Style {
+                                        size: Size::new(
+                                            Val::Px(ACHIEVEMENT_ICON_SIZE / 2.0),
+                                            Val::Px(ACHIEVEMENT_ICON_SIZE / 2.0),
+                                        ),
+                                        ..default()
+                                    },
+                                    image: asset_server.load(definition.icon.as_str()).into(),
+                                    background_color: icon_color.into(),
+                                    ..default()
+                                });
+                            });
+
+                        let progress = match (unlocked, lifetime_progress) {
+                            (true, _) => 1.0,
+                            (false, Some((current, target))) => current as f32 / target as f32,
+                            (false, None) => 0.0,
+                        };
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    size: Size::new(
+                                        Val::Px(ACHIEVEMENT_ICON_SIZE),
+                                        Val::Px(ACHIEVEMENT_BAR_HEIGHT),
+                                    ),
+                                    margin: UiRect::top(Val::Px(ACHIEVEMENT_SPACING / 4.0)),
+                                    ..default()
+                                },
+                                background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(NodeBundle {
+                                    style: Style {
+                                        size: Size::new(
+                                            Val::Percent(progress * 100.0),
+                                            Val::Percent(100.0),
+                                        ),
+                                        ..default()
+                                    },
+                                    background_color: Color::rgb(0.9, 0.8, 0.2).into(),
+                                    ..default()
+                                });
+                            });
+                    });
+            }
+        });
+}
+
+pub fn despawn_achievements_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<AchievementsScreen>>,
+) {
+    for screen_entity in screen_query.iter() {
+        commands.entity(screen_entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,28 @@
+mod components;
+mod systems;
+
+use crate::game::in_versus_mode;
+use crate::AppState;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const WINNER_ICON_SIZE: f32 = 128.0;
+pub const RESULT_ICON_SIZE: f32 = 32.0;
+pub const RESULT_BAR_WIDTH: f32 = 300.0;
+pub const RESULT_BAR_HEIGHT: f32 = 16.0;
+
+pub struct GameOverScreenPlugin;
+
+impl Plugin for GameOverScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Upon finishing a versus match, show who won it
+            .add_system(
+                spawn_winner_screen
+                    .run_if(in_versus_mode)
+                    .in_schedule(OnEnter(AppState::GameOver)),
+            )
+            .add_system(despawn_winner_screen.in_schedule(OnExit(AppState::GameOver)));
+    }
+}
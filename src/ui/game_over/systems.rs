@@ -0,0 +1,102 @@
+use super::components::*;
+use super::{RESULT_BAR_HEIGHT, RESULT_BAR_WIDTH, RESULT_ICON_SIZE, WINNER_ICON_SIZE};
+use crate::game::versus::resources::MatchResult;
+
+use bevy::prelude::*;
+
+// Shows the winner's ball in the middle of the screen with everyone's score below it.
+pub fn spawn_winner_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    match_result: Res<MatchResult>,
+) {
+    let best_score = match_result
+        .players
+        .iter()
+        .map(|player| player.score)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            WinnerScreen {},
+        ))
+        .with_children(|parent| {
+            // A tie has no winner, show an empty star instead.
+            let (winner_texture, winner_color) = match match_result.winner {
+                Some(winner) => ("sprites/Default/ball_blue_large.png", winner.color()),
+                None => ("sprites/Default/star_outline.png", Color::WHITE),
+            };
+
+            parent.spawn(ImageBundle {
+                style: Style {
+                    size: Size::new(Val::Px(WINNER_ICON_SIZE), Val::Px(WINNER_ICON_SIZE)),
+                    margin: UiRect::bottom(Val::Px(RESULT_ICON_SIZE)),
+                    ..default()
+                },
+                image: asset_server.load(winner_texture).into(),
+                background_color: winner_color.into(),
+                ..default()
+            });
+
+            for player in match_result.players.iter() {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::bottom(Val::Px(RESULT_BAR_HEIGHT / 2.0)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(ImageBundle {
+                            style: Style {
+                                size: Size::new(
+                                    Val::Px(RESULT_ICON_SIZE),
+                                    Val::Px(RESULT_ICON_SIZE),
+                                ),
+                                margin: UiRect::right(Val::Px(RESULT_BAR_HEIGHT / 2.0)),
+                                ..default()
+                            },
+                            image: asset_server
+                                .load("sprites/Default/ball_blue_small.png")
+                                .into(),
+                            background_color: player.id.color().into(),
+                            ..default()
+                        });
+
+                        let bar_width = RESULT_BAR_WIDTH * player.score as f32 / best_score as f32;
+                        parent.spawn(NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(bar_width), Val::Px(RESULT_BAR_HEIGHT)),
+                                ..default()
+                            },
+                            background_color: player.id.color().into(),
+                            ..default()
+                        });
+                    });
+            }
+        });
+}
+
+pub fn despawn_winner_screen(
+    mut commands: Commands,
+    winner_screen_query: Query<Entity, With<WinnerScreen>>,
+) {
+    for winner_screen_entity in winner_screen_query.iter() {
+        commands.entity(winner_screen_entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,58 @@
+use crate::game::player::components::PlayerId;
+
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct Hud {}
+
+// Row of key icons showing how many keys are left on the level.
+#[derive(Component)]
+pub struct KeyHud {}
+
+// Row of bullets showing the ammo left in the shooter mode.
+#[derive(Component)]
+pub struct AmmoHud {}
+
+// Row of life icons of the given player.
+#[derive(Component)]
+pub struct LivesHud {
+    pub player_id: PlayerId,
+}
+
+// Bar that fills up while the player's dash is recharging.
+#[derive(Component)]
+pub struct DashCooldownFill {
+    pub player_id: PlayerId,
+}
+
+// Health bar at the top of the screen, only shown while a boss is alive.
+#[derive(Component)]
+pub struct BossHealthBar {}
+
+#[derive(Component)]
+pub struct BossHealthFill {}
+
+#[derive(Component)]
+pub struct BossHealthPercentage {}
+
+// Score in the top right corner.
+#[derive(Component)]
+pub struct ScoreHud {}
+
+// Multiplier under the score, only shown while a combo is going.
+#[derive(Component)]
+pub struct ComboHud {}
+
+// Versus mode bar showing how close the player is to the score target.
+#[derive(Component)]
+pub struct ScoreProgressFill {
+    pub player_id: PlayerId,
+}
+
+// Versus mode bar showing the time left in the match.
+#[derive(Component)]
+pub struct MatchTimerFill {}
+
+// Bar growing right while the player is ahead of the ghost's best run, left while behind.
+#[derive(Component)]
+pub struct DeltaToBestFill {}
@@ -0,0 +1,58 @@
+mod components;
+mod systems;
+
+use crate::game::ghost::resources::GhostRun;
+use crate::game::{in_puzzle_mode, in_versus_mode, shooting_enabled};
+use crate::AppState;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const HUD_MARGIN: f32 = 10.0;
+pub const HUD_ICON_SPACING: f32 = 4.0;
+pub const AMMO_ICON_SIZE: f32 = 12.0;
+pub const LIFE_ICON_SIZE: f32 = 16.0;
+pub const BOSS_HEALTH_BAR_HEIGHT: f32 = 16.0;
+pub const DASH_BAR_WIDTH: f32 = 100.0;
+pub const DASH_BAR_HEIGHT: f32 = 8.0;
+pub const DELTA_TO_BEST_RANGE: f32 = 10.0; // Points ahead or behind that fill half of the bar.
+pub const SCORE_SCALE: f32 = 1.0;
+pub const COMBO_SCALE: f32 = 0.75;
+pub const BOSS_HEALTH_SCALE: f32 = 0.5;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Upon entering the game state, spawn the HUD
+            .add_system(spawn_hud.in_schedule(OnEnter(AppState::Game)))
+            // Upon leaving the game state, despawn the HUD
+            .add_system(despawn_hud.in_schedule(OnExit(AppState::Game)))
+            .add_system(
+                update_key_hud
+                    .run_if(in_puzzle_mode)
+                    .in_set(OnUpdate(AppState::Game)),
+            )
+            .add_system(
+                update_ammo_hud
+                    .run_if(shooting_enabled)
+                    .in_set(OnUpdate(AppState::Game)),
+            )
+            .add_system(update_boss_health_bar.in_set(OnUpdate(AppState::Game)))
+            .add_system(update_score_hud.in_set(OnUpdate(AppState::Game)))
+            .add_system(update_combo_hud.in_set(OnUpdate(AppState::Game)))
+            .add_system(update_dash_cooldown_bar.in_set(OnUpdate(AppState::Game)))
+            .add_system(update_lives_hud.in_set(OnUpdate(AppState::Game)))
+            .add_system(
+                update_delta_to_best_bar
+                    .run_if(resource_exists::<GhostRun>())
+                    .in_set(OnUpdate(AppState::Game)),
+            )
+            .add_systems(
+                (update_score_progress_bars, update_match_timer_bar)
+                    .distributive_run_if(in_versus_mode)
+                    .in_set(OnUpdate(AppState::Game)),
+            );
+    }
+}
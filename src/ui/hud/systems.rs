@@ -0,0 +1,510 @@
+use super::components::*;
+use super::{
+    AMMO_ICON_SIZE, BOSS_HEALTH_BAR_HEIGHT, BOSS_HEALTH_SCALE, COMBO_SCALE, DASH_BAR_HEIGHT,
+    DASH_BAR_WIDTH, DELTA_TO_BEST_RANGE, HUD_ICON_SPACING, HUD_MARGIN, LIFE_ICON_SIZE, SCORE_SCALE,
+};
+use crate::game::boss::components::Boss;
+use crate::game::boss::BOSS_HEALTH;
+use crate::game::ghost::resources::GhostRun;
+use crate::game::objective::resources::KeyCount;
+use crate::game::player::components::{DashCooldown, Lives, Player, PlayerId};
+use crate::game::player::resources::PlayerCount;
+use crate::game::projectile::resources::Ammo;
+use crate::game::score::resources::{Combo, Score};
+use crate::game::simulation::resources::SimulationClock;
+use crate::game::versus::resources::VersusMatch;
+use crate::game::versus::VERSUS_SCORE_TARGET;
+use crate::game::GameMode;
+use crate::ui::number::components::{NumberAlign, NumberFormat, NumberText};
+
+use bevy::prelude::*;
+
+pub fn spawn_hud(
+    mut commands: Commands,
+    player_count: Res<PlayerCount>,
+    game_mode: Res<GameMode>,
+    ghost_run: Option<Res<GhostRun>>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        left: Val::Px(HUD_MARGIN),
+                        top: Val::Px(HUD_MARGIN),
+                        ..default()
+                    },
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            Hud {},
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        ..default()
+                    },
+                    ..default()
+                },
+                KeyHud {},
+            ));
+
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        margin: UiRect::top(Val::Px(HUD_ICON_SPACING)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                AmmoHud {},
+            ));
+
+            // Lives and dash cooldown of every player.
+            for index in 0..player_count.value {
+                let player_id = PlayerId(index);
+
+                parent.spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Row,
+                            margin: UiRect::top(Val::Px(HUD_ICON_SPACING)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    LivesHud { player_id },
+                ));
+
+                spawn_bar(parent, player_id.color(), DashCooldownFill { player_id });
+
+                if *game_mode == GameMode::Versus {
+                    spawn_bar(parent, player_id.color(), ScoreProgressFill { player_id });
+                }
+            }
+
+            if ghost_run.is_some() {
+                spawn_bar(parent, Color::WHITE, DeltaToBestFill {});
+            }
+        });
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        right: Val::Px(HUD_MARGIN),
+                        top: Val::Px(HUD_MARGIN),
+                        ..default()
+                    },
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::FlexEnd,
+                    ..default()
+                },
+                ..default()
+            },
+            Hud {},
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle::default(),
+                NumberText {
+                    align: NumberAlign::Right,
+                    scale: SCORE_SCALE,
+                    ..default()
+                },
+                ScoreHud {},
+            ));
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        display: Display::None,
+                        margin: UiRect::top(Val::Px(HUD_ICON_SPACING)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                NumberText {
+                    format: NumberFormat::Multiplier,
+                    align: NumberAlign::Right,
+                    scale: COMBO_SCALE,
+                    color: Color::rgb(1.0, 0.85, 0.3),
+                    ..default()
+                },
+                ComboHud {},
+            ));
+        });
+
+    if *game_mode == GameMode::Versus {
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position: UiRect {
+                            left: Val::Percent(25.0),
+                            bottom: Val::Px(HUD_MARGIN),
+                            ..default()
+                        },
+                        size: Size::new(Val::Percent(50.0), Val::Px(DASH_BAR_HEIGHT)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+                    ..default()
+                },
+                Hud {},
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                            ..default()
+                        },
+                        background_color: Color::rgb(0.9, 0.9, 0.9).into(),
+                        ..default()
+                    },
+                    MatchTimerFill {},
+                ));
+            });
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        left: Val::Percent(25.0),
+                        top: Val::Px(HUD_MARGIN),
+                        ..default()
+                    },
+                    size: Size::new(Val::Percent(50.0), Val::Px(BOSS_HEALTH_BAR_HEIGHT)),
+                    ..default()
+                },
+                background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+                ..default()
+            },
+            Hud {},
+            BossHealthBar {},
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.85, 0.2, 0.2).into(),
+                    ..default()
+                },
+                BossHealthFill {},
+            ));
+            // Laid over the bar.
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                NumberText {
+                    format: NumberFormat::Percentage,
+                    align: NumberAlign::Center,
+                    scale: BOSS_HEALTH_SCALE,
+                    ..default()
+                },
+                BossHealthPercentage {},
+            ));
+        });
+}
+
+// Spawns a small bar with the fill marked by the given component.
+fn spawn_bar(parent: &mut ChildBuilder, color: Color, fill: impl Component) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(DASH_BAR_WIDTH), Val::Px(DASH_BAR_HEIGHT)),
+                margin: UiRect::top(Val::Px(HUD_ICON_SPACING)),
+                ..default()
+            },
+            background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(0.0), Val::Percent(100.0)),
+                        ..default()
+                    },
+                    background_color: color.into(),
+                    ..default()
+                },
+                fill,
+            ));
+        });
+}
+
+pub fn despawn_hud(mut commands: Commands, hud_query: Query<Entity, With<Hud>>) {
+    for hud_entity in hud_query.iter() {
+        commands.entity(hud_entity).despawn_recursive();
+    }
+}
+
+// Rebuilds the key icons whenever a key gets collected: outlines for the keys
+// that are still on the level, filled keys for the collected ones.
+pub fn update_key_hud(
+    mut commands: Commands,
+    key_hud_query: Query<Entity, With<KeyHud>>,
+    asset_server: Res<AssetServer>,
+    key_count: Res<KeyCount>,
+) {
+    if !key_count.is_changed() {
+        return;
+    }
+
+    let key_hud_entity = match key_hud_query.get_single() {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    commands.entity(key_hud_entity).despawn_descendants();
+    commands.entity(key_hud_entity).with_children(|parent| {
+        for key_index in 0..key_count.total {
+            let texture = if key_index < key_count.remaining {
+                "sprites/Default/key_outline.png"
+            } else {
+                "sprites/Default/key.png"
+            };
+
+            parent.spawn(ImageBundle {
+                style: Style {
+                    size: Size::new(Val::Px(37.0), Val::Px(34.0)),
+                    margin: UiRect::right(Val::Px(HUD_ICON_SPACING)),
+                    ..default()
+                },
+                image: asset_server.load(texture).into(),
+                ..default()
+            });
+        }
+    });
+}
+
+// Shows the boss health bar while the boss is alive and shrinks it as the boss gets hurt.
+pub fn update_boss_health_bar(
+    mut health_bar_query: Query<&mut Style, (With<BossHealthBar>, Without<BossHealthFill>)>,
+    mut health_fill_query: Query<&mut Style, (With<BossHealthFill>, Without<BossHealthBar>)>,
+    mut health_percentage_query: Query<&mut NumberText, With<BossHealthPercentage>>,
+    boss_query: Query<&Boss>,
+) {
+    let (mut bar_style, mut fill_style) = match (
+        health_bar_query.get_single_mut(),
+        health_fill_query.get_single_mut(),
+    ) {
+        (Ok(bar_style), Ok(fill_style)) => (bar_style, fill_style),
+        _ => return,
+    };
+
+    match boss_query.get_single() {
+        Ok(boss) => {
+            bar_style.display = Display::Flex;
+            fill_style.size.width = Val::Percent(boss.health as f32 / BOSS_HEALTH as f32 * 100.0);
+
+            let percentage = boss.health * 100 / BOSS_HEALTH;
+            for mut number_text in health_percentage_query.iter_mut() {
+                if number_text.value != percentage {
+                    number_text.value = percentage;
+                }
+            }
+        }
+        Err(_) => bar_style.display = Display::None,
+    }
+}
+
+pub fn update_score_hud(
+    mut score_hud_query: Query<&mut NumberText, With<ScoreHud>>,
+    score: Res<Score>,
+) {
+    if !score.is_changed() {
+        return;
+    }
+
+    for mut number_text in score_hud_query.iter_mut() {
+        number_text.value = score.value;
+    }
+}
+
+// Shows the multiplier while the combo is long enough to raise it.
+pub fn update_combo_hud(
+    mut combo_hud_query: Query<(&mut NumberText, &mut Style), With<ComboHud>>,
+    combo: Res<Combo>,
+) {
+    let multiplier = combo.multiplier();
+
+    for (mut number_text, mut style) in combo_hud_query.iter_mut() {
+        let display = if multiplier > 1 {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if style.display != display {
+            style.display = display;
+        }
+        if number_text.value != multiplier {
+            number_text.value = multiplier;
+        }
+    }
+}
+
+// Rebuilds the ammo icons whenever the ammo changes.
+pub fn update_ammo_hud(
+    mut commands: Commands,
+    ammo_hud_query: Query<Entity, With<AmmoHud>>,
+    asset_server: Res<AssetServer>,
+    ammo: Res<Ammo>,
+) {
+    if !ammo.is_changed() {
+        return;
+    }
+
+    let ammo_hud_entity = match ammo_hud_query.get_single() {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    commands.entity(ammo_hud_entity).despawn_descendants();
+    commands.entity(ammo_hud_entity).with_children(|parent| {
+        for _ in 0..ammo.value {
+            parent.spawn(ImageBundle {
+                style: Style {
+                    size: Size::new(Val::Px(AMMO_ICON_SIZE), Val::Px(AMMO_ICON_SIZE)),
+                    margin: UiRect::right(Val::Px(HUD_ICON_SPACING / 2.0)),
+                    ..default()
+                },
+                image: asset_server
+                    .load("sprites/Default/ball_blue_small.png")
+                    .into(),
+                ..default()
+            });
+        }
+    });
+}
+
+pub fn update_dash_cooldown_bar(
+    mut dash_fill_query: Query<(&mut Style, &DashCooldownFill)>,
+    player_query: Query<(&Player, &DashCooldown)>,
+) {
+    for (mut fill_style, dash_fill) in dash_fill_query.iter_mut() {
+        // Players that are out have their bar emptied.
+        let percent = player_query
+            .iter()
+            .find(|(player, _)| player.id == dash_fill.player_id)
+            .map(|(_, dash_cooldown)| dash_cooldown.timer.percent())
+            .unwrap_or(0.0);
+
+        fill_style.size.width = Val::Percent(percent * 100.0);
+    }
+}
+
+// Rebuilds the life icons of the players that lost or got out.
+pub fn update_lives_hud(
+    mut commands: Commands,
+    lives_hud_query: Query<(Entity, &LivesHud)>,
+    changed_lives_query: Query<(), Changed<Lives>>,
+    player_query: Query<(&Player, &Lives)>,
+    mut removed_players: RemovedComponents<Player>,
+    asset_server: Res<AssetServer>,
+) {
+    let players_removed = removed_players.iter().count() > 0;
+    if changed_lives_query.is_empty() && !players_removed {
+        return;
+    }
+
+    for (lives_hud_entity, lives_hud) in lives_hud_query.iter() {
+        let lives = player_query
+            .iter()
+            .find(|(player, _)| player.id == lives_hud.player_id)
+            .map(|(_, lives)| lives.value)
+            .unwrap_or(0);
+
+        commands.entity(lives_hud_entity).despawn_descendants();
+        commands.entity(lives_hud_entity).with_children(|parent| {
+            for _ in 0..lives {
+                parent.spawn(ImageBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(LIFE_ICON_SIZE), Val::Px(LIFE_ICON_SIZE)),
+                        margin: UiRect::right(Val::Px(HUD_ICON_SPACING)),
+                        ..default()
+                    },
+                    image: asset_server
+                        .load("sprites/Default/ball_blue_small.png")
+                        .into(),
+                    background_color: lives_hud.player_id.color().into(),
+                    ..default()
+                });
+            }
+        });
+    }
+}
+
+// Shows how close every player is to the versus score target.
+pub fn update_score_progress_bars(
+    mut score_fill_query: Query<(&mut Style, &ScoreProgressFill)>,
+    score: Res<Score>,
+) {
+    if !score.is_changed() {
+        return;
+    }
+
+    for (mut fill_style, score_fill) in score_fill_query.iter_mut() {
+        let progress = score.player_score(score_fill.player_id) as f32 / VERSUS_SCORE_TARGET as f32;
+        fill_style.size.width = Val::Percent(progress.min(1.0) * 100.0);
+    }
+}
+
+// Shrinks the match timer bar as the versus match goes on.
+pub fn update_match_timer_bar(
+    mut timer_fill_query: Query<&mut Style, With<MatchTimerFill>>,
+    versus_match: Res<VersusMatch>,
+) {
+    if let Ok(mut fill_style) = timer_fill_query.get_single_mut() {
+        fill_style.size.width = Val::Percent(versus_match.timer.percent_left() * 100.0);
+    }
+}
+
+// Compares the score with the one the ghost's run had at the same moment.
+pub fn update_delta_to_best_bar(
+    mut delta_fill_query: Query<(&mut Style, &mut BackgroundColor), With<DeltaToBestFill>>,
+    ghost_run: Res<GhostRun>,
+    score: Res<Score>,
+    simulation_clock: Res<SimulationClock>,
+) {
+    let (mut fill_style, mut fill_color) = match delta_fill_query.get_single_mut() {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    let delta = score.value as f32 - ghost_run.score_at(simulation_clock.tick) as f32;
+    let half_width = (delta.abs() / DELTA_TO_BEST_RANGE).min(1.0) * 50.0;
+
+    fill_style.position_type = PositionType::Absolute;
+    fill_style.size.width = Val::Percent(half_width);
+    if delta >= 0.0 {
+        fill_style.position = UiRect::left(Val::Percent(50.0));
+        *fill_color = Color::rgb(0.3, 0.9, 0.3).into();
+    } else {
+        fill_style.position = UiRect::right(Val::Percent(50.0));
+        *fill_color = Color::rgb(0.9, 0.3, 0.3).into();
+    }
+}
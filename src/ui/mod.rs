@@ -1,10 +1,33 @@
+mod achievements;
+mod game_over;
+mod hud;
+pub mod number;
+mod popup;
+mod settings;
+mod stats;
+
+use achievements::AchievementsUIPlugin;
+use game_over::GameOverScreenPlugin;
+use hud::HudPlugin;
+use number::NumberPlugin;
+use popup::ScorePopupPlugin;
+use settings::SettingsUIPlugin;
+use stats::StatsUIPlugin;
+
 use bevy::prelude::*;
 
 pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
-        app.add_startup_system(init_ui);
+        app.add_plugin(HudPlugin)
+            .add_plugin(NumberPlugin)
+            .add_plugin(ScorePopupPlugin)
+            .add_plugin(GameOverScreenPlugin)
+            .add_plugin(AchievementsUIPlugin)
+            .add_plugin(StatsUIPlugin)
+            .add_plugin(SettingsUIPlugin)
+            .add_startup_system(init_ui);
     }
 }
 
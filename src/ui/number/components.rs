@@ -0,0 +1,75 @@
+use super::{CROSS_GLYPH, PERCENTAGE_GLYPH, PLUS_GLYPH};
+
+use bevy::prelude::*;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum NumberFormat {
+    #[default]
+    Integer,
+    // Written as "x3".
+    Multiplier,
+    // Written as "45%".
+    Percentage,
+    // Written as "+5".
+    Gain,
+}
+
+// Where the number sits relative to its entity, in the UI it's where the glyphs gather
+// inside the node.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum NumberAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+// Number written out with glyph sprites. On a UI node the glyphs are laid out as images,
+// anywhere else as sprites around the entity. The glyphs are kept and reused when the
+// number changes.
+#[derive(Component, Debug, Clone)]
+pub struct NumberText {
+    pub value: usize,
+    pub format: NumberFormat,
+    pub align: NumberAlign,
+    pub scale: f32,
+    pub color: Color,
+}
+
+impl Default for NumberText {
+    fn default() -> Self {
+        NumberText {
+            value: 0,
+            format: NumberFormat::Integer,
+            align: NumberAlign::Left,
+            scale: 1.0,
+            color: Color::WHITE,
+        }
+    }
+}
+
+impl NumberText {
+    // Indices of the glyphs spelling the number out.
+    pub fn glyphs(&self) -> Vec<usize> {
+        let mut glyphs = Vec::new();
+        match self.format {
+            NumberFormat::Multiplier => glyphs.push(CROSS_GLYPH),
+            NumberFormat::Gain => glyphs.push(PLUS_GLYPH),
+            _ => {}
+        }
+        glyphs.extend(
+            self.value
+                .to_string()
+                .bytes()
+                .map(|digit| (digit - b'0') as usize),
+        );
+        if self.format == NumberFormat::Percentage {
+            glyphs.push(PERCENTAGE_GLYPH);
+        }
+        glyphs
+    }
+}
+
+// Single glyph of a NumberText.
+#[derive(Component)]
+pub struct NumberGlyph {}
@@ -0,0 +1,42 @@
+pub mod components;
+pub mod resources;
+mod systems;
+
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+// Sizes of the glyph sprites: the digits from 0 to 9, the cross, the percentage and the plus,
+// which is the cross turned around.
+pub const GLYPH_SIZES: [(f32, f32); 13] = [
+    (22.0, 29.0),
+    (18.0, 28.0),
+    (21.0, 28.0),
+    (22.0, 33.0),
+    (24.0, 31.0),
+    (22.0, 33.0),
+    (23.0, 30.0),
+    (21.0, 30.0),
+    (23.0, 30.0),
+    (22.0, 31.0),
+    (19.0, 24.0),
+    (28.0, 31.0),
+    (24.0, 24.0),
+];
+pub const CROSS_GLYPH: usize = 10;
+pub const PERCENTAGE_GLYPH: usize = 11;
+pub const PLUS_GLYPH: usize = 12;
+pub const GLYPH_SPACING: f32 = 2.0;
+
+// Writes numbers out with the number sprites, both in the UI and in the world.
+pub struct NumberPlugin;
+
+impl Plugin for NumberPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NumberFont>()
+            .add_startup_system(load_number_font)
+            .add_system(update_ui_numbers)
+            .add_system(update_sprite_numbers);
+    }
+}
@@ -0,0 +1,63 @@
+use super::components::*;
+use super::{CROSS_GLYPH, GLYPH_SIZES, GLYPH_SPACING, PLUS_GLYPH};
+
+use bevy::prelude::*;
+use std::f32::consts::FRAC_PI_4;
+
+// Glyph sprite with its size, rotation and where its center is, relative to the number's position.
+pub struct PlacedGlyph {
+    pub texture: Handle<Image>,
+    pub size: Vec2,
+    pub position: Vec2,
+    pub rotation: f32,
+}
+
+// Textures of the glyphs, indexed like GLYPH_SIZES. The plus has none of its own.
+#[derive(Resource, Default)]
+pub struct NumberFont {
+    pub glyphs: Vec<Handle<Image>>,
+}
+
+impl NumberFont {
+    // Lays the glyphs of the number out on a line, standing on a common baseline
+    // and lined up by the number's alignment.
+    pub fn layout(&self, number_text: &NumberText) -> Vec<PlacedGlyph> {
+        let glyphs = number_text.glyphs();
+        let sizes: Vec<Vec2> = glyphs
+            .iter()
+            .map(|glyph| Vec2::from(GLYPH_SIZES[*glyph]) * number_text.scale)
+            .collect();
+
+        let spacing = GLYPH_SPACING * number_text.scale;
+        let width = sizes.iter().map(|size| size.x).sum::<f32>()
+            + spacing * glyphs.len().saturating_sub(1) as f32;
+        let height = sizes.iter().map(|size| size.y).fold(0.0, f32::max);
+
+        let mut x = match number_text.align {
+            NumberAlign::Left => 0.0,
+            NumberAlign::Center => -width / 2.0,
+            NumberAlign::Right => -width,
+        };
+
+        glyphs
+            .iter()
+            .zip(sizes)
+            .map(|(glyph, size)| {
+                let position = Vec2::new(x + size.x / 2.0, (size.y - height) / 2.0);
+                x += size.x + spacing;
+
+                // The plus takes the room of the turned cross, the sprite is the cross itself.
+                let (texture, rotation) = match *glyph {
+                    PLUS_GLYPH => (CROSS_GLYPH, FRAC_PI_4),
+                    _ => (*glyph, 0.0),
+                };
+                PlacedGlyph {
+                    texture: self.glyphs.get(texture).cloned().unwrap_or_default(),
+                    size: Vec2::from(GLYPH_SIZES[texture]) * number_text.scale,
+                    position,
+                    rotation,
+                }
+            })
+            .collect()
+    }
+}
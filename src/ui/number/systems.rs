@@ -0,0 +1,181 @@
+use super::components::*;
+use super::resources::*;
+use super::GLYPH_SPACING;
+
+use bevy::prelude::*;
+
+pub fn load_number_font(mut number_font: ResMut<NumberFont>, asset_server: Res<AssetServer>) {
+    let mut paths: Vec<String> = (0..10)
+        .map(|digit| format!("sprites/Default/number_{}.png", digit))
+        .collect();
+    paths.push("sprites/Default/number_cross.png".to_string());
+    paths.push("sprites/Default/number_percentage.png".to_string());
+
+    number_font.glyphs = paths
+        .iter()
+        .map(|path| asset_server.load(path.as_str()))
+        .collect();
+}
+
+// Lays the glyphs of the changed UI numbers out in a row inside their node.
+#[allow(clippy::type_complexity)]
+pub fn update_ui_numbers(
+    mut commands: Commands,
+    mut number_query: Query<
+        (Entity, &NumberText, &mut Style, Option<&Children>),
+        (Changed<NumberText>, Without<NumberGlyph>),
+    >,
+    mut glyph_query: Query<
+        (
+            &mut UiImage,
+            &mut Style,
+            &mut BackgroundColor,
+            &mut Transform,
+        ),
+        (With<NumberGlyph>, Without<NumberText>),
+    >,
+    number_font: Res<NumberFont>,
+) {
+    for (number_entity, number_text, mut number_style, children) in number_query.iter_mut() {
+        number_style.flex_direction = FlexDirection::Row;
+        number_style.align_items = AlignItems::FlexEnd;
+        number_style.justify_content = match number_text.align {
+            NumberAlign::Left => JustifyContent::FlexStart,
+            NumberAlign::Center => JustifyContent::Center,
+            NumberAlign::Right => JustifyContent::FlexEnd,
+        };
+
+        let glyphs = number_font.layout(number_text);
+        let glyph_entities: Vec<Entity> = children
+            .map(|children| {
+                children
+                    .iter()
+                    .filter(|entity| glyph_query.contains(**entity))
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        for (index, glyph) in glyphs.iter().enumerate() {
+            let style = Style {
+                size: Size::new(Val::Px(glyph.size.x), Val::Px(glyph.size.y)),
+                margin: UiRect::left(Val::Px(if index == 0 {
+                    0.0
+                } else {
+                    GLYPH_SPACING * number_text.scale
+                })),
+                ..default()
+            };
+
+            match glyph_entities
+                .get(index)
+                .and_then(|entity| glyph_query.get_mut(*entity).ok())
+            {
+                Some((mut image, mut glyph_style, mut color, mut transform)) => {
+                    image.texture = glyph.texture.clone();
+                    *glyph_style = style;
+                    *color = number_text.color.into();
+                    transform.rotation = Quat::from_rotation_z(glyph.rotation);
+                }
+                None => {
+                    commands.entity(number_entity).with_children(|parent| {
+                        parent.spawn((
+                            ImageBundle {
+                                style,
+                                image: glyph.texture.clone().into(),
+                                background_color: number_text.color.into(),
+                                transform: Transform::from_rotation(Quat::from_rotation_z(
+                                    glyph.rotation,
+                                )),
+                                ..default()
+                            },
+                            NumberGlyph {},
+                        ));
+                    });
+                }
+            }
+        }
+
+        // Glyphs left over from a longer number.
+        for glyph_entity in glyph_entities.iter().skip(glyphs.len()) {
+            if let Ok((_, mut glyph_style, _, _)) = glyph_query.get_mut(*glyph_entity) {
+                glyph_style.display = Display::None;
+            }
+        }
+    }
+}
+
+// Places the glyphs of the changed numbers in the world around their entity.
+#[allow(clippy::type_complexity)]
+pub fn update_sprite_numbers(
+    mut commands: Commands,
+    number_query: Query<
+        (Entity, &NumberText, Option<&Children>),
+        (Changed<NumberText>, Without<Node>),
+    >,
+    mut glyph_query: Query<
+        (
+            &mut Handle<Image>,
+            &mut Sprite,
+            &mut Transform,
+            &mut Visibility,
+        ),
+        With<NumberGlyph>,
+    >,
+    number_font: Res<NumberFont>,
+) {
+    for (number_entity, number_text, children) in number_query.iter() {
+        let glyphs = number_font.layout(number_text);
+        let glyph_entities: Vec<Entity> = children
+            .map(|children| {
+                children
+                    .iter()
+                    .filter(|entity| glyph_query.contains(**entity))
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        for (index, glyph) in glyphs.iter().enumerate() {
+            let sprite = Sprite {
+                color: number_text.color,
+                custom_size: Some(glyph.size),
+                ..default()
+            };
+            let transform = Transform::from_translation(glyph.position.extend(0.0))
+                .with_rotation(Quat::from_rotation_z(glyph.rotation));
+
+            match glyph_entities
+                .get(index)
+                .and_then(|entity| glyph_query.get_mut(*entity).ok())
+            {
+                Some((mut texture, mut glyph_sprite, mut glyph_transform, mut visibility)) => {
+                    *texture = glyph.texture.clone();
+                    *glyph_sprite = sprite;
+                    *glyph_transform = transform;
+                    *visibility = Visibility::Inherited;
+                }
+                None => {
+                    commands.entity(number_entity).with_children(|parent| {
+                        parent.spawn((
+                            SpriteBundle {
+                                transform,
+                                texture: glyph.texture.clone(),
+                                sprite,
+                                ..default()
+                            },
+                            NumberGlyph {},
+                        ));
+                    });
+                }
+            }
+        }
+
+        // Glyphs left over from a longer number.
+        for glyph_entity in glyph_entities.iter().skip(glyphs.len()) {
+            if let Ok((_, _, _, mut visibility)) = glyph_query.get_mut(*glyph_entity) {
+                *visibility = Visibility::Hidden;
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+// Pooled popup, hidden while it's not in use.
+#[derive(Component)]
+pub struct ScorePopup {
+    pub timer: Timer,
+    pub active: bool,
+}
+
+#[derive(Component)]
+pub struct PopupPoints {}
+
+#[derive(Component)]
+pub struct PopupMultiplier {}
@@ -0,0 +1,37 @@
+mod components;
+mod systems;
+
+use crate::game::simulation::run_simulation;
+use crate::game::GameState;
+use crate::AppState;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const POPUP_POOL_SIZE: usize = 16; // Popups ready up front, the oldest one is reused past that.
+pub const POPUP_LIFETIME: f32 = 0.8;
+pub const POPUP_RISE_SPEED: f32 = 60.0;
+pub const POPUP_Z: f32 = 2.0; // Above the particles.
+pub const POPUP_SCALE: f32 = 0.6;
+pub const POPUP_MULTIPLIER_SCALE: f32 = 0.45;
+pub const POPUP_MULTIPLIER_OFFSET: f32 = 18.0; // How far below the points the multiplier sits.
+
+// Floating "+N" over every collected star.
+pub struct ScorePopupPlugin;
+
+impl Plugin for ScorePopupPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Upon entering the game state, fill the pool
+            .add_system(spawn_popup_pool.in_schedule(OnEnter(AppState::Game)))
+            .add_system(despawn_popups.in_schedule(OnExit(AppState::Game)))
+            .add_systems(
+                (
+                    show_score_popups.after(run_simulation),
+                    update_score_popups.after(show_score_popups),
+                )
+                    .in_set(OnUpdate(AppState::Game))
+                    .in_set(OnUpdate(GameState::Running)),
+            );
+    }
+}
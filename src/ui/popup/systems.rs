@@ -0,0 +1,145 @@
+use super::components::*;
+use super::{
+    POPUP_LIFETIME, POPUP_MULTIPLIER_OFFSET, POPUP_MULTIPLIER_SCALE, POPUP_POOL_SIZE,
+    POPUP_RISE_SPEED, POPUP_SCALE, POPUP_Z,
+};
+use crate::events::StarCollected;
+use crate::ui::number::components::{NumberAlign, NumberFormat, NumberGlyph, NumberText};
+
+use bevy::prelude::*;
+
+const MULTIPLIER_COLOR: Color = Color::rgb(1.0, 0.85, 0.3);
+
+pub fn spawn_popup_pool(mut commands: Commands) {
+    for _ in 0..POPUP_POOL_SIZE {
+        commands
+            .spawn((
+                SpatialBundle {
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                ScorePopup {
+                    timer: Timer::from_seconds(POPUP_LIFETIME, TimerMode::Once),
+                    active: false,
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    SpatialBundle::default(),
+                    NumberText {
+                        format: NumberFormat::Gain,
+                        align: NumberAlign::Center,
+                        scale: POPUP_SCALE,
+                        ..default()
+                    },
+                    PopupPoints {},
+                ));
+                parent.spawn((
+                    SpatialBundle::from_transform(Transform::from_xyz(
+                        0.0,
+                        -POPUP_MULTIPLIER_OFFSET,
+                        0.0,
+                    )),
+                    NumberText {
+                        format: NumberFormat::Multiplier,
+                        align: NumberAlign::Center,
+                        scale: POPUP_MULTIPLIER_SCALE,
+                        color: MULTIPLIER_COLOR,
+                        ..default()
+                    },
+                    PopupMultiplier {},
+                ));
+            });
+    }
+}
+
+pub fn despawn_popups(mut commands: Commands, popup_query: Query<Entity, With<ScorePopup>>) {
+    for popup_entity in popup_query.iter() {
+        commands.entity(popup_entity).despawn_recursive();
+    }
+}
+
+// Takes a popup from the pool for every collected star, the oldest one still showing
+// if they're all in use. The multiplier is shown when the combo raised the points.
+#[allow(clippy::type_complexity)]
+pub fn show_score_popups(
+    mut star_collected_event_reader: EventReader<StarCollected>,
+    mut popup_query: Query<(&mut ScorePopup, &mut Transform, &mut Visibility, &Children)>,
+    mut points_query: Query<&mut NumberText, (With<PopupPoints>, Without<PopupMultiplier>)>,
+    mut multiplier_query: Query<
+        (&mut NumberText, &mut Visibility),
+        (
+            With<PopupMultiplier>,
+            Without<PopupPoints>,
+            Without<ScorePopup>,
+        ),
+    >,
+) {
+    for event in star_collected_event_reader.iter() {
+        let popup = popup_query.iter_mut().max_by(|(a, ..), (b, ..)| {
+            let age = |popup: &ScorePopup| {
+                if popup.active {
+                    popup.timer.elapsed_secs()
+                } else {
+                    f32::INFINITY
+                }
+            };
+            age(a).total_cmp(&age(b))
+        });
+        let (mut popup, mut transform, mut visibility, children) = match popup {
+            Some(v) => v,
+            None => return,
+        };
+
+        popup.active = true;
+        popup.timer.reset();
+        transform.translation = event.position.truncate().extend(POPUP_Z);
+        *visibility = Visibility::Inherited;
+
+        for child in children.iter() {
+            if let Ok(mut number_text) = points_query.get_mut(*child) {
+                number_text.value = event.points;
+            }
+            if let Ok((mut number_text, mut visibility)) = multiplier_query.get_mut(*child) {
+                number_text.value = event.multiplier;
+                *visibility = if event.multiplier > 1 {
+                    Visibility::Inherited
+                } else {
+                    Visibility::Hidden
+                };
+            }
+        }
+    }
+}
+
+// Raises the popups and fades them out, putting them back in the pool once they're done.
+// The glyph sprites are faded on their own, the numbers don't have to be laid out again.
+pub fn update_score_popups(
+    mut popup_query: Query<(&mut ScorePopup, &mut Transform, &mut Visibility, &Children)>,
+    number_query: Query<&Children, With<NumberText>>,
+    mut glyph_query: Query<&mut Sprite, With<NumberGlyph>>,
+    time: Res<Time>,
+) {
+    for (mut popup, mut transform, mut visibility, children) in popup_query.iter_mut() {
+        if !popup.active {
+            continue;
+        }
+
+        popup.timer.tick(time.delta());
+        if popup.timer.finished() {
+            popup.active = false;
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        transform.translation.y += POPUP_RISE_SPEED * time.delta_seconds();
+
+        // Fully visible for the first half, fading out over the second one.
+        let alpha = (popup.timer.percent_left() * 2.0).min(1.0);
+        for glyph_entity in number_query.iter_many(children).flatten() {
+            if let Ok(mut sprite) = glyph_query.get_mut(*glyph_entity) {
+                sprite.color.set_a(alpha);
+            }
+        }
+    }
+}
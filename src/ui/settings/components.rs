@@ -0,0 +1,20 @@
+use crate::settings::resources::Setting;
+
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct SettingsScreen {
+    // Index of the highlighted line.
+    pub selected: usize,
+}
+
+// Line of the settings screen, the bar shows the setting's value.
+#[derive(Component)]
+pub struct SettingRow {
+    pub index: usize,
+}
+
+#[derive(Component)]
+pub struct SettingBar {
+    pub setting: Setting,
+}
@@ -0,0 +1,45 @@
+mod components;
+mod systems;
+
+use crate::game::GameState;
+use crate::AppState;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const SETTINGS_SPACING: f32 = 8.0;
+pub const SETTING_ROW_WIDTH: f32 = 320.0;
+pub const SETTING_ROW_HEIGHT: f32 = 24.0;
+pub const SETTING_BAR_HEIGHT: f32 = 8.0;
+pub const SELECTED_ROW_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.25);
+
+pub struct SettingsUIPlugin;
+
+impl Plugin for SettingsUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                toggle_settings_screen,
+                navigate_settings.after(toggle_settings_screen),
+                change_setting.after(navigate_settings),
+                update_setting_rows.after(change_setting),
+            )
+                .distributive_run_if(settings_reachable),
+        )
+        .add_system(despawn_settings_screen.in_schedule(OnExit(AppState::Menu)))
+        .add_system(despawn_settings_screen.in_schedule(OnExit(AppState::Game)))
+        .add_system(despawn_settings_screen.in_schedule(OnEnter(GameState::Running)));
+    }
+}
+
+// The settings can be changed from the menu, or from the game while it's paused.
+pub fn settings_reachable(
+    app_state: Res<State<AppState>>,
+    game_state: Res<State<GameState>>,
+) -> bool {
+    match app_state.0 {
+        AppState::Menu => true,
+        AppState::Game => game_state.0 == GameState::Paused,
+        AppState::GameOver => false,
+    }
+}
@@ -0,0 +1,185 @@
+use super::components::*;
+use super::{
+    SELECTED_ROW_COLOR, SETTINGS_SPACING, SETTING_BAR_HEIGHT, SETTING_ROW_HEIGHT, SETTING_ROW_WIDTH,
+};
+use crate::events::{Sound, SoundEvent};
+use crate::game::player::components::PlayerId;
+use crate::settings::resources::{Setting, Settings};
+
+use bevy::prelude::*;
+
+// Opens or closes the settings screen. Up and down pick a setting, left and right change it.
+pub fn toggle_settings_screen(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    screen_query: Query<Entity, With<SettingsScreen>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::O) {
+        return;
+    }
+
+    if let Ok(screen_entity) = screen_query.get_single() {
+        commands.entity(screen_entity).despawn_recursive();
+        return;
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+                ..default()
+            },
+            SettingsScreen { selected: 0 },
+        ))
+        .with_children(|parent| {
+            for (index, setting) in Setting::all().into_iter().enumerate() {
+                parent
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                size: Size::new(
+                                    Val::Px(SETTING_ROW_WIDTH),
+                                    Val::Px(SETTING_ROW_HEIGHT),
+                                ),
+                                align_items: AlignItems::Center,
+                                padding: UiRect::horizontal(Val::Px(SETTINGS_SPACING)),
+                                margin: UiRect::bottom(Val::Px(SETTINGS_SPACING / 2.0)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        SettingRow { index },
+                    ))
+                    .with_children(|parent| {
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    size: Size::new(
+                                        Val::Percent(100.0),
+                                        Val::Px(SETTING_BAR_HEIGHT),
+                                    ),
+                                    ..default()
+                                },
+                                background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    NodeBundle {
+                                        style: Style {
+                                            size: Size::new(Val::Percent(0.0), Val::Percent(100.0)),
+                                            ..default()
+                                        },
+                                        background_color: setting_color(setting).into(),
+                                        ..default()
+                                    },
+                                    SettingBar { setting },
+                                ));
+                            });
+                    });
+            }
+        });
+}
+
+// Audio settings are yellow, video blue, gameplay green and the bindings take the player's tint.
+fn setting_color(setting: Setting) -> Color {
+    match setting {
+        Setting::MasterVolume | Setting::SfxVolume | Setting::MusicVolume | Setting::UiVolume => {
+            Color::rgb(0.9, 0.8, 0.2)
+        }
+        Setting::DisplayMode | Setting::Resolution | Setting::Vsync => Color::rgb(0.3, 0.6, 1.0),
+        Setting::ScreenShake | Setting::Ghost => Color::rgb(0.3, 0.8, 0.3),
+        Setting::Binding(player) => PlayerId(player).color(),
+    }
+}
+
+pub fn navigate_settings(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut screen_query: Query<&mut SettingsScreen>,
+    settings: Res<Settings>,
+) {
+    let mut settings_screen = match screen_query.get_single_mut() {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+    let settings_num = Setting::all().len();
+
+    if keyboard_input.just_pressed(KeyCode::Down) {
+        settings_screen.selected = (settings_screen.selected + 1) % settings_num;
+    } else if keyboard_input.just_pressed(KeyCode::Up) {
+        settings_screen.selected = (settings_screen.selected + settings_num - 1) % settings_num;
+    } else {
+        return;
+    }
+
+    println!(
+        "{}",
+        settings.describe(Setting::all()[settings_screen.selected])
+    );
+}
+
+// Changes the highlighted setting and saves the settings right away.
+pub fn change_setting(
+    keyboard_input: Res<Input<KeyCode>>,
+    screen_query: Query<&SettingsScreen>,
+    mut settings: ResMut<Settings>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+) {
+    let settings_screen = match screen_query.get_single() {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    let forwards = if keyboard_input.just_pressed(KeyCode::Right) {
+        true
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        false
+    } else {
+        return;
+    };
+
+    let setting = Setting::all()[settings_screen.selected];
+    settings.change(setting, forwards);
+    settings.save();
+    sound_event_writer.send(Sound::SettingChanged.into());
+    println!("{}", settings.describe(setting));
+}
+
+pub fn update_setting_rows(
+    screen_query: Query<&SettingsScreen>,
+    mut row_query: Query<(&SettingRow, &mut BackgroundColor)>,
+    mut bar_query: Query<(&SettingBar, &mut Style)>,
+    settings: Res<Settings>,
+) {
+    let settings_screen = match screen_query.get_single() {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    for (row, mut background_color) in row_query.iter_mut() {
+        *background_color = if row.index == settings_screen.selected {
+            SELECTED_ROW_COLOR.into()
+        } else {
+            Color::NONE.into()
+        };
+    }
+    for (bar, mut style) in bar_query.iter_mut() {
+        style.size.width = Val::Percent(settings.fraction(bar.setting) * 100.0);
+    }
+}
+
+pub fn despawn_settings_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<SettingsScreen>>,
+) {
+    for screen_entity in screen_query.iter() {
+        commands.entity(screen_entity).despawn_recursive();
+    }
+}
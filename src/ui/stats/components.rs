@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// Bar charts of the lifetime stats, opened from the menu.
+#[derive(Component)]
+pub struct StatsScreen {}
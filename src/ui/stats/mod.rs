@@ -0,0 +1,23 @@
+mod components;
+mod systems;
+
+use crate::AppState;
+use systems::*;
+
+use bevy::prelude::*;
+
+pub const STATS_SPACING: f32 = 16.0;
+pub const STATS_ICON_SIZE: f32 = 32.0;
+pub const STATS_ROW_WIDTH: f32 = 300.0;
+pub const STATS_ROW_HEIGHT: f32 = 16.0;
+pub const SCORE_CHART_HEIGHT: f32 = 120.0;
+pub const SCORE_BAR_WIDTH: f32 = 12.0;
+
+pub struct StatsUIPlugin;
+
+impl Plugin for StatsUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(toggle_stats_screen.run_if(in_state(AppState::Menu)))
+            .add_system(despawn_stats_screen.in_schedule(OnExit(AppState::Menu)));
+    }
+}
@@ -0,0 +1,194 @@
+use super::components::*;
+use super::{
+    SCORE_BAR_WIDTH, SCORE_CHART_HEIGHT, STATS_ICON_SIZE, STATS_ROW_HEIGHT, STATS_ROW_WIDTH,
+    STATS_SPACING,
+};
+use crate::events::HitSource;
+use crate::game::stats::resources::LifetimeStats;
+
+use bevy::prelude::*;
+
+// Opens or closes the stats screen: the scores of the latest runs with a line at the average,
+// the longest and the average survival, and what the players got knocked out by.
+pub fn toggle_stats_screen(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    screen_query: Query<Entity, With<StatsScreen>>,
+    asset_server: Res<AssetServer>,
+    lifetime_stats: Res<LifetimeStats>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::S) {
+        return;
+    }
+
+    if let Ok(screen_entity) = screen_query.get_single() {
+        commands.entity(screen_entity).despawn_recursive();
+        return;
+    }
+
+    println!("Runs: {}", lifetime_stats.runs);
+    println!("Stars: {}", lifetime_stats.stars);
+    println!("Play time: {:.0}s", lifetime_stats.play_time);
+    println!("Longest survival: {:.1}s", lifetime_stats.longest_survival);
+    println!("Average score: {:.1}", lifetime_stats.average_score());
+    println!("Best combo: {}", lifetime_stats.best_combo);
+    println!("Deaths: {:?}", lifetime_stats.deaths);
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+                ..default()
+            },
+            StatsScreen {},
+        ))
+        .with_children(|parent| {
+            spawn_score_chart(parent, &lifetime_stats);
+
+            let longest_survival = lifetime_stats.longest_survival.max(f32::EPSILON);
+            spawn_stat_row(
+                parent,
+                asset_server.load("sprites/Default/ball_blue_large.png"),
+                lifetime_stats.longest_survival / longest_survival,
+                Color::rgb(0.3, 0.6, 1.0),
+            );
+            spawn_stat_row(
+                parent,
+                asset_server.load("sprites/Default/ball_blue_large_alt.png"),
+                lifetime_stats.average_survival() / longest_survival,
+                Color::rgb(0.3, 0.6, 1.0),
+            );
+
+            let death_count =
+                |source: HitSource| lifetime_stats.deaths.get(&source).copied().unwrap_or(0);
+            let most_deaths = death_count(HitSource::Enemy)
+                .max(death_count(HitSource::Boss))
+                .max(1);
+            for (source, texture) in [
+                (HitSource::Enemy, "sprites/Default/ball_red_large.png"),
+                (HitSource::Boss, "sprites/Default/ball_red_large_alt.png"),
+            ] {
+                spawn_stat_row(
+                    parent,
+                    asset_server.load(texture),
+                    death_count(source) as f32 / most_deaths as f32,
+                    Color::rgb(0.9, 0.3, 0.3),
+                );
+            }
+        });
+}
+
+// Column for every one of the latest runs, as high as its score.
+fn spawn_score_chart(parent: &mut ChildBuilder, lifetime_stats: &LifetimeStats) {
+    let best_score = lifetime_stats
+        .recent_scores
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or(0)
+        .max(1) as f32;
+
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::height(Val::Px(SCORE_CHART_HEIGHT)),
+                align_items: AlignItems::FlexEnd,
+                margin: UiRect::bottom(Val::Px(STATS_SPACING)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for score in lifetime_stats.recent_scores.iter() {
+                parent.spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(
+                            Val::Px(SCORE_BAR_WIDTH),
+                            Val::Percent(*score as f32 / best_score * 100.0),
+                        ),
+                        margin: UiRect::horizontal(Val::Px(SCORE_BAR_WIDTH / 4.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.9, 0.8, 0.2).into(),
+                    ..default()
+                });
+            }
+
+            // Average score line across the columns.
+            parent.spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect::bottom(Val::Percent(
+                        lifetime_stats.average_score() / best_score * 100.0,
+                    )),
+                    size: Size::new(Val::Percent(100.0), Val::Px(2.0)),
+                    ..default()
+                },
+                background_color: Color::WHITE.into(),
+                ..default()
+            });
+        });
+}
+
+// Icon followed by a bar filled up to the given fraction.
+fn spawn_stat_row(parent: &mut ChildBuilder, icon: Handle<Image>, fraction: f32, color: Color) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                margin: UiRect::bottom(Val::Px(STATS_SPACING / 2.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(ImageBundle {
+                style: Style {
+                    size: Size::new(Val::Px(STATS_ICON_SIZE), Val::Px(STATS_ICON_SIZE)),
+                    margin: UiRect::right(Val::Px(STATS_SPACING / 2.0)),
+                    ..default()
+                },
+                image: icon.into(),
+                ..default()
+            });
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(STATS_ROW_WIDTH), Val::Px(STATS_ROW_HEIGHT)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(NodeBundle {
+                        style: Style {
+                            size: Size::new(
+                                Val::Percent(fraction.clamp(0.0, 1.0) * 100.0),
+                                Val::Percent(100.0),
+                            ),
+                            ..default()
+                        },
+                        background_color: color.into(),
+                        ..default()
+                    });
+                });
+        });
+}
+
+pub fn despawn_stats_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<StatsScreen>>,
+) {
+    for screen_entity in screen_query.iter() {
+        commands.entity(screen_entity).despawn_recursive();
+    }
+}
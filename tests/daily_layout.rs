@@ -0,0 +1,84 @@
+use bevy_first_game::game::daily::resources::*;
+use bevy_first_game::game::enemy::components::Enemy;
+use bevy_first_game::game::simulation::resources::SimulationClock;
+use bevy_first_game::game::simulation::step_simulation;
+use bevy_first_game::game::star::components::Star;
+use bevy_first_game::game::{GameMode, GamePlugin};
+use bevy_first_game::network::headless_plugins;
+use bevy_first_game::AppState;
+
+use bevy::prelude::*;
+
+const CHECK_DAY: i64 = 19_700; // Any fixed date will do.
+const LAYOUT_TICKS: usize = 180; // Long enough for the first stars and enemies to spawn over time.
+
+#[test]
+fn every_day_rotates_the_mutator_and_the_seed() {
+    for day in CHECK_DAY..CHECK_DAY + 7 {
+        assert_eq!(
+            Mutator::from_seed(daily_seed(day)),
+            Mutator::for_day(day),
+            "Seed of day {} carries the wrong mutator",
+            day
+        );
+        assert_ne!(
+            Mutator::for_day(day),
+            Mutator::for_day(day + 1),
+            "The mutator didn't rotate after day {}",
+            day
+        );
+    }
+    assert_eq!(daily_seed(CHECK_DAY), daily_seed(CHECK_DAY));
+    assert_ne!(daily_seed(CHECK_DAY), daily_seed(CHECK_DAY + 1));
+}
+
+// Fixes the date and checks that the daily challenge always lays out the same.
+#[test]
+fn the_same_day_always_lays_out_the_same() {
+    let layout = daily_layout(CHECK_DAY);
+    assert!(!layout.is_empty(), "Nothing got spawned");
+    assert_eq!(
+        layout,
+        daily_layout(CHECK_DAY),
+        "The same day got a different layout"
+    );
+    assert_ne!(
+        layout,
+        daily_layout(CHECK_DAY + 1),
+        "Two days got the same layout"
+    );
+}
+
+// Plays the first ticks of the given day's challenge without any input and returns where
+// the stars and the enemies are.
+fn daily_layout(day: i64) -> Vec<(bool, [u32; 2])> {
+    let mut app = App::new();
+    app.add_plugins(headless_plugins())
+        .add_state::<AppState>()
+        .add_plugin(GamePlugin)
+        .insert_resource(GameMode::Daily)
+        .insert_resource(DailyCalendar {
+            fixed_day: Some(day),
+        });
+    app.setup();
+
+    // Ticks are stepped here, the frame time would make every layout different.
+    app.world.resource_mut::<SimulationClock>().manual = true;
+    app.world.insert_resource(NextState(Some(AppState::Game)));
+    app.update();
+    for _ in 0..LAYOUT_TICKS {
+        step_simulation(&mut app.world, true);
+    }
+
+    let mut layout: Vec<(bool, [u32; 2])> = app
+        .world
+        .query_filtered::<(&Transform, Option<&Star>), Or<(With<Star>, With<Enemy>)>>()
+        .iter(&app.world)
+        .map(|(transform, star)| {
+            let position = transform.translation.truncate().to_array();
+            (star.is_some(), position.map(f32::to_bits))
+        })
+        .collect();
+    layout.sort();
+    layout
+}
@@ -0,0 +1,49 @@
+use bevy_first_game::game::enemy::components::Enemy;
+use bevy_first_game::game::enemy::ENEMIES_NUM;
+use bevy_first_game::game::star::components::Star;
+use bevy_first_game::game::star::STARS_NUM;
+use bevy_first_game::game::GamePlugin;
+use bevy_first_game::network::headless_plugins;
+use bevy_first_game::AppState;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::{Duration, Instant};
+
+const SHORT_FRAME: Duration = Duration::from_millis(4); // A 250 Hz display, well under a tick.
+const SHORT_FRAMES_NUM: u32 = 8;
+
+// The run has to start the same however fast the frames are, even when the first few
+// frames go by without a single simulation tick.
+#[test]
+fn short_frames_keep_the_initial_layout() {
+    let mut app = App::new();
+    app.add_plugins(headless_plugins())
+        .add_state::<AppState>()
+        .add_plugin(GamePlugin);
+    app.setup();
+
+    let start = Instant::now();
+    app.world
+        .insert_resource(TimeUpdateStrategy::ManualInstant(start));
+    app.world.insert_resource(NextState(Some(AppState::Game)));
+    for frame in 0..SHORT_FRAMES_NUM {
+        app.world.insert_resource(TimeUpdateStrategy::ManualInstant(
+            start + SHORT_FRAME * frame,
+        ));
+        app.update();
+    }
+
+    let enemies_num = app
+        .world
+        .query_filtered::<(), With<Enemy>>()
+        .iter(&app.world)
+        .count();
+    let stars_num = app
+        .world
+        .query_filtered::<(), With<Star>>()
+        .iter(&app.world)
+        .count();
+    assert_eq!(enemies_num, ENEMIES_NUM, "Initial enemies went missing");
+    assert_eq!(stars_num, STARS_NUM, "Initial stars went missing");
+}
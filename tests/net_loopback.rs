@@ -0,0 +1,126 @@
+use bevy_first_game::game::player::components::{Player, PlayerId};
+use bevy_first_game::network::client::resources::{ServerConnection, SnapshotBuffer};
+use bevy_first_game::network::client::ClientPlugin;
+use bevy_first_game::network::headless_plugins;
+use bevy_first_game::network::messages::NetEntityKind;
+use bevy_first_game::network::server::resources::ConnectedClients;
+use bevy_first_game::network::server::server_app;
+use bevy_first_game::network::transport::{LoopbackNetwork, NetTransport};
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use std::net::SocketAddr;
+use std::thread;
+use std::time::Duration;
+
+const LOOPBACK_FRAME_TIME: Duration = Duration::from_millis(16);
+
+// Runs the server and two clients in one process over an in-memory network and checks
+// that they can connect, play and leave.
+#[test]
+fn clients_connect_play_and_leave() {
+    let network = LoopbackNetwork::default();
+    let server_address: SocketAddr = "127.0.0.1:7777".parse().unwrap();
+
+    let mut server = server_app(NetTransport::new(network.endpoint(server_address)));
+    let mut clients: Vec<App> = ["127.0.0.1:7778", "127.0.0.1:7779"]
+        .iter()
+        .map(|address| {
+            let address: SocketAddr = address.parse().unwrap();
+            let mut client = App::new();
+            client
+                .add_plugins(headless_plugins())
+                .add_plugin(ClientPlugin { server_address })
+                .insert_resource(NetTransport::new(network.endpoint(address)));
+            client
+        })
+        .collect();
+
+    server.setup();
+    for client in clients.iter_mut() {
+        client.setup();
+    }
+
+    // Both clients join and get their own player.
+    run_frames(&mut server, &mut clients, 60);
+
+    let player_ids: Vec<PlayerId> = clients
+        .iter()
+        .map(|client| {
+            client
+                .world
+                .resource::<ServerConnection>()
+                .player_id
+                .expect("Client didn't get a player id")
+        })
+        .collect();
+    assert_ne!(
+        player_ids[0], player_ids[1],
+        "Clients got the same player id"
+    );
+    assert_eq!(server_players(&mut server).len(), 2);
+
+    for client in clients.iter() {
+        let (_, snapshot) = client
+            .world
+            .resource::<SnapshotBuffer>()
+            .snapshots
+            .back()
+            .expect("Client didn't get any snapshot");
+        let players_num = snapshot
+            .entities
+            .iter()
+            .filter(|entity| matches!(entity.kind, NetEntityKind::Player(_)))
+            .count();
+        assert_eq!(players_num, 2, "Client doesn't see both players");
+    }
+
+    // The first client's input moves their player on the server.
+    let start_x = server_player_x(&mut server, player_ids[0]);
+    clients[0]
+        .world
+        .resource_mut::<Input<KeyCode>>()
+        .press(KeyCode::D);
+    run_frames(&mut server, &mut clients, 30);
+    assert!(
+        server_player_x(&mut server, player_ids[0]) > start_x,
+        "Player didn't move to the right"
+    );
+
+    // The second client leaves and its player is gone.
+    clients[1].world.send_event(AppExit);
+    run_frames(&mut server, &mut clients, 10);
+    assert_eq!(server.world.resource::<ConnectedClients>().clients.len(), 1);
+    assert_eq!(server_players(&mut server), vec![player_ids[0]]);
+}
+
+fn run_frames(server: &mut App, clients: &mut [App], frames: usize) {
+    for _ in 0..frames {
+        for client in clients.iter_mut() {
+            client.update();
+        }
+        server.update();
+        thread::sleep(LOOPBACK_FRAME_TIME);
+    }
+}
+
+fn server_players(server: &mut App) -> Vec<PlayerId> {
+    let mut player_ids: Vec<PlayerId> = server
+        .world
+        .query::<&Player>()
+        .iter(&server.world)
+        .map(|player| player.id)
+        .collect();
+    player_ids.sort_by_key(|player_id| player_id.0);
+    player_ids
+}
+
+fn server_player_x(server: &mut App, player_id: PlayerId) -> f32 {
+    server
+        .world
+        .query::<(&Player, &Transform)>()
+        .iter(&server.world)
+        .find(|(player, _)| player.id == player_id)
+        .map(|(_, transform)| transform.translation.x)
+        .expect("Player is missing on the server")
+}
@@ -0,0 +1,144 @@
+use bevy_first_game::game::player::resources::PlayerCount;
+use bevy_first_game::game::replay::resources::{
+    Replay, ReplayPlayback, ReplayRecorder, TrackPoint,
+};
+use bevy_first_game::game::replay::MIN_PLAYBACK_SPEED;
+use bevy_first_game::game::simulation::resources::{GameRng, SimulationClock};
+use bevy_first_game::game::{GameMode, GamePlugin};
+use bevy_first_game::network::headless_plugins;
+use bevy_first_game::AppState;
+
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::{Duration, Instant};
+
+// Frames shorter and longer than a tick, so some run no tick and some run several.
+const FRAME_TIMES: [Duration; 3] = [
+    Duration::from_millis(5),
+    Duration::from_millis(40),
+    Duration::from_millis(9),
+];
+const RECORDED_FRAMES: usize = 90;
+const PLAYBACK_FRAME_TIME: Duration = Duration::from_millis(16);
+const MAX_PLAYBACK_FRAMES: usize = 5_000;
+
+// Runs and watched replays are driven by a clock the test controls.
+struct TestApp {
+    app: App,
+    now: Instant,
+}
+
+impl TestApp {
+    fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins(headless_plugins())
+            .add_state::<AppState>()
+            .add_plugin(GamePlugin);
+        app.setup();
+
+        let now = Instant::now();
+        app.world
+            .insert_resource(TimeUpdateStrategy::ManualInstant(now));
+        TestApp { app, now }
+    }
+
+    fn frame(&mut self, frame_time: Duration) {
+        self.now += frame_time;
+        self.app
+            .world
+            .insert_resource(TimeUpdateStrategy::ManualInstant(self.now));
+        self.app.update();
+    }
+
+    fn key(&mut self, key_code: KeyCode, state: ButtonState) {
+        self.app.world.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key_code),
+            state,
+        });
+    }
+}
+
+// Plays a run with moves and dashes, then watches it at the slowest speed. The watched run
+// has to go through the same points on every tick as the recorded one.
+#[test]
+fn replay_follows_the_recorded_run() {
+    let recorded = record_run();
+    assert!(!recorded.track.is_empty(), "Nothing got recorded");
+
+    let watched = watch_replay(&recorded);
+    assert_eq!(watched.len(), recorded.track.len(), "Replay ended early");
+    for (tick, (watched, recorded)) in watched.iter().zip(recorded.track.iter()).enumerate() {
+        assert_eq!(watched, recorded, "Replay diverged on tick {}", tick);
+    }
+}
+
+fn record_run() -> Replay {
+    let mut test_app = TestApp::new();
+    test_app
+        .app
+        .world
+        .insert_resource(NextState(Some(AppState::Game)));
+
+    for frame in 0..RECORDED_FRAMES {
+        match frame % 30 {
+            0 => test_app.key(KeyCode::D, ButtonState::Pressed),
+            10 => test_app.key(KeyCode::LShift, ButtonState::Pressed),
+            11 => test_app.key(KeyCode::LShift, ButtonState::Released),
+            15 => test_app.key(KeyCode::D, ButtonState::Released),
+            16 => test_app.key(KeyCode::W, ButtonState::Pressed),
+            25 => test_app.key(KeyCode::W, ButtonState::Released),
+            _ => {}
+        }
+        test_app.frame(FRAME_TIMES[frame % FRAME_TIMES.len()]);
+    }
+
+    test_app
+        .app
+        .world
+        .resource::<ReplayRecorder>()
+        .replay
+        .clone()
+        .expect("The run wasn't recorded")
+}
+
+// Watches the replay the way the menu starts it, recording the watched ticks along the way.
+fn watch_replay(replay: &Replay) -> Vec<TrackPoint> {
+    let mut test_app = TestApp::new();
+    let world = &mut test_app.app.world;
+    *world.resource_mut::<GameMode>() = replay.game_mode;
+    world.resource_mut::<PlayerCount>().value = replay.player_count;
+    world.insert_resource(GameRng::new(replay.seed));
+    world.resource_mut::<SimulationClock>().manual = true;
+    let mut replay_playback = ReplayPlayback::new(replay.clone());
+    replay_playback.speed = MIN_PLAYBACK_SPEED;
+    world.insert_resource(replay_playback);
+    world.insert_resource(NextState(Some(AppState::Game)));
+    test_app.frame(PLAYBACK_FRAME_TIME);
+
+    // Watched runs aren't recorded by the game, this one is only to compare the ticks.
+    test_app.app.world.resource_mut::<ReplayRecorder>().replay = Some(Replay::new(
+        replay.seed,
+        replay.game_mode,
+        replay.player_count,
+        replay.config_hash,
+    ));
+
+    for _ in 0..MAX_PLAYBACK_FRAMES {
+        if !test_app.app.world.contains_resource::<ReplayPlayback>() {
+            break;
+        }
+        test_app.frame(PLAYBACK_FRAME_TIME);
+    }
+
+    test_app
+        .app
+        .world
+        .resource::<ReplayRecorder>()
+        .replay
+        .as_ref()
+        .map(|replay| replay.track.clone())
+        .unwrap_or_default()
+}